@@ -0,0 +1,159 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! This module contains the standard [`WhitePoint`] illuminants, and [`chromatic_adapt`]
+//! for converting [`Xyz`] colors between them.
+
+use crate::types::Xyz;
+
+/// The white point of a standard illuminant, for the CIE 1931 2° standard observer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WhitePoint {
+    /// Incandescent (tungsten) light, about 2856 K.
+    A,
+    /// Average daylight, about 6774 K. Superseded by [`WhitePoint::D65`].
+    C,
+    /// Horizon daylight, about 5003 K. The white point of ICC profiles and most print workflows.
+    D50,
+    /// Mid-morning daylight, about 5503 K.
+    D55,
+    /// Noon daylight, about 6504 K. The white point of sRGB.
+    D65,
+    /// North sky daylight, about 7504 K.
+    D75,
+    /// The equal-energy illuminant.
+    E,
+    /// Cool white fluorescent, about 4230 K.
+    F2,
+    /// Broadband daylight fluorescent, about 6500 K.
+    F7,
+    /// Narrowband white fluorescent, about 4000 K.
+    F11,
+}
+
+impl WhitePoint {
+    /// Returns the `(x, y)` chromaticity coordinates of the white point.
+    pub fn chromaticity(&self) -> (f64, f64) {
+        match self {
+            Self::A => (0.44757, 0.40745),
+            Self::C => (0.31006, 0.31616),
+            Self::D50 => (0.34567, 0.35850),
+            Self::D55 => (0.33242, 0.34743),
+            Self::D65 => (0.31270, 0.32900),
+            Self::D75 => (0.29902, 0.31485),
+            Self::E => (1.0 / 3.0, 1.0 / 3.0),
+            Self::F2 => (0.37208, 0.37529),
+            Self::F7 => (0.31292, 0.32933),
+            Self::F11 => (0.38052, 0.37713),
+        }
+    }
+
+    /// Returns the white point in [`Xyz`], normalized so that `y` is `1.0`.
+    pub fn xyz(&self) -> Xyz {
+        let (x, y) = self.chromaticity();
+
+        Xyz {
+            x: x / y,
+            y: 1.0,
+            z: (1.0 - x - y) / y,
+        }
+    }
+}
+
+/// Converts a color that was measured under the `from` illuminant to the color that
+/// appears the same under the `to` illuminant, using the Bradford transform.
+///
+/// This is needed whenever [`Xyz`] (or [`crate::Lab`]) values cross between illuminants,
+/// for example when moving between sRGB (D65) and print (D50) workflows.
+pub fn chromatic_adapt(xyz: Xyz, from: WhitePoint, to: WhitePoint) -> Xyz {
+    if from == to {
+        return xyz;
+    }
+
+    chromatic_adapt_xyz(xyz, from.xyz(), to.xyz())
+}
+
+/// Similar to [`chromatic_adapt`], but the white points are given as their tristimulus values,
+/// for illuminants that are not a [`WhitePoint`], such as one measured from a display.
+/// The white points should have a `y` of `1.0`.
+pub fn chromatic_adapt_xyz(xyz: Xyz, from: Xyz, to: Xyz) -> Xyz {
+    let source = multiply(&BRADFORD, from.into());
+    let destination = multiply(&BRADFORD, to.into());
+    let cone = multiply(&BRADFORD, xyz.into());
+
+    let scaled = [
+        cone[0] * destination[0] / source[0],
+        cone[1] * destination[1] / source[1],
+        cone[2] * destination[2] / source[2],
+    ];
+
+    Xyz::from(multiply(&BRADFORD_INVERSE, scaled))
+}
+
+//
+// Math helpers
+//
+
+/// XYZ to the Bradford cone response domain.
+const BRADFORD: [[f64; 3]; 3] = [
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+];
+
+/// The inverse of [`BRADFORD`].
+const BRADFORD_INVERSE: [[f64; 3]; 3] = [
+    [0.9869929054667121, -0.1470542564209901, 0.15996265166373125],
+    [0.4323052697233945, 0.5183602715367776, 0.049291228212855615],
+    [-0.008528664575177331, 0.04004282165408486, 0.96848669578755],
+];
+
+fn multiply(matrix: &[[f64; 3]; 3], vector: [f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn assert_close(actual: Xyz, expected: Xyz) {
+        let (actual, expected): ([f64; 3], [f64; 3]) = (actual.into(), expected.into());
+
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-9, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test_case(WhitePoint::D65, WhitePoint::D50 ; "d65 to d50")]
+    #[test_case(WhitePoint::D50, WhitePoint::D65 ; "d50 to d65")]
+    #[test_case(WhitePoint::A, WhitePoint::F11 ; "a to f11")]
+    fn test_white_maps_to_white(from: WhitePoint, to: WhitePoint) {
+        assert_close(chromatic_adapt(from.xyz(), from, to), to.xyz());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let color = Xyz::from([0.3, 0.2, 0.6]);
+        let adapted = chromatic_adapt(color, WhitePoint::D65, WhitePoint::D50);
+
+        assert_ne!(adapted, color);
+        assert_close(
+            chromatic_adapt(adapted, WhitePoint::D50, WhitePoint::D65),
+            color,
+        );
+    }
+}
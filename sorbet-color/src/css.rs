@@ -18,10 +18,12 @@
 //! in functional notation. This module typically will not be used directly.
 //! See the [`crate::Color::new`] constructor for more information.
 
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 use thiserror::Error;
 
+use crate::{Color, Rgba};
+
 /// Variants of this enum are used when the [`crate::Color::new`] constructor fails to parse an input string.
 /// View the source code for the descriptions of these variants.
 #[allow(missing_docs)]
@@ -35,18 +37,55 @@ pub enum Error {
     InvalidHexChars,
     #[error("the input string began with a format identifier but was missing parenthesis")]
     MissingCssParens,
-    #[error(
-        "the input string had a segment that was assumed to be an integer but failed to parse"
-    )]
-    InvalidCssFloat,
-    #[error("the input string had a number that ended with a percent symbol but failed to parse as a float")]
-    InvalidCssPercent,
+    #[error("the input string had `{found}` at byte {index}, which was assumed to be a number but failed to parse")]
+    InvalidCssFloat { index: usize, found: String },
+    #[error("the input string had `{found}` at byte {index}, which ended with a percent symbol but failed to parse as a float")]
+    InvalidCssPercent { index: usize, found: String },
     #[error("the input string was assumed to be CSS functional notation but did not the correct number of values")]
     InvalidCssParams,
     #[error("tried to parse into a color structure but failed because the input string had the wrong format")]
     WrongCssFormat,
-    #[error("the input string had a prefix indicating a format that is not supported")]
-    UnknownCssFormat,
+    #[error("the input string had the prefix `{found}` at byte {index}, which is not a supported format")]
+    UnknownCssFormat { index: usize, found: String },
+    #[error("the input string had a channel outside of its valid range")]
+    ValueOutOfRange,
+    #[error("the named color entry `{0}` was missing a colon between the name and the color")]
+    MissingNameSeparator(String),
+    #[error("the color for the name `{name}` failed to parse: {source}")]
+    InvalidNamedColor { name: String, source: Box<Error> },
+    #[error("the color at index {index} of the list failed to parse: {source}")]
+    InvalidListItem { index: usize, source: Box<Error> },
+    #[error("`{input}` is not a hexadecimal color ({hex}), nor a functional notation ({functional}), nor a named color")]
+    UnrecognizedColor {
+        input: String,
+        hex: Box<Error>,
+        functional: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Returns the byte offset of the part of the input string that the error is about,
+    /// for the variants that have one.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Self::InvalidCssFloat { index, .. }
+            | Self::InvalidCssPercent { index, .. }
+            | Self::UnknownCssFormat { index, .. } => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Moves the byte offset of the error forward by `offset`, for when the string that failed
+    /// to parse was part of a larger one.
+    fn offset_by(mut self, offset: usize) -> Self {
+        match &mut self {
+            Self::InvalidCssFloat { index, .. }
+            | Self::InvalidCssPercent { index, .. }
+            | Self::UnknownCssFormat { index, .. } => *index += offset,
+            _ => {}
+        }
+        self
+    }
 }
 
 /// The [`std::result::Result`] alias returned from parsing operations from this module.
@@ -72,6 +111,33 @@ pub enum CssNumber {
     Float(f64),
 }
 
+/// The kinds of channel in the CSS color functions, which decide how a [`CssNumber`] is scaled
+/// and brought into range by [`CssNumber::clamp_channel`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChannelKind {
+    /// A red, green, or blue channel, where a float is ranged `0.0..255.0`
+    /// and `100%` is the maximum. The result is clamped to `0.0..=1.0`.
+    Rgb,
+    /// A hue in degrees, where `100%` is a full turn.
+    /// The result is wrapped into `0.0..360.0`, so that `-30` becomes `330`.
+    Hue,
+    /// A channel such as saturation, lightness, or alpha, where a float is ranged `0.0..1.0`
+    /// and `100%` is the maximum. The result is clamped to `0.0..=1.0`.
+    Unit,
+}
+
+/// The two syntaxes of the CSS color functions, for serializing colors for a particular browser engine.
+/// See [`crate::Color::to_css_hsl`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CssSyntax {
+    /// The comma-separated syntax of CSS Color 3, with a separate function for alpha,
+    /// such as `hsl(210, 50%, 40%)` and `hsla(210, 50%, 40%, 0.5)`. Every engine supports this.
+    Legacy,
+    /// The space-separated syntax of CSS Color 4, with the alpha after a slash,
+    /// such as `hsl(210 50% 40%)` and `hsl(210 50% 40% / 0.5)`.
+    Modern,
+}
+
 /// This enumerable represents the names of the CSS color functions supported by the crate.
 #[allow(missing_docs)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, strum::EnumString, strum::Display)]
@@ -83,6 +149,44 @@ pub enum CssColorType {
     Hsva,
     Hsl,
     Hsla,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+}
+
+impl CssColorType {
+    /// Returns `true` for the formats introduced by CSS Color 4, which are written
+    /// with space-separated channels and have an optional alpha channel.
+    pub fn is_modern(&self) -> bool {
+        matches!(self, Self::Lab | Self::Lch | Self::Oklab | Self::Oklch)
+    }
+
+    /// Returns the kind of each channel of this format, in order, including the alpha channel.
+    /// The channels of the CSS Color 4 formats that are not bounded, such as the `a` and `b` of `lab()`,
+    /// are [`None`].
+    pub fn channel_kinds(&self) -> [Option<ChannelKind>; 4] {
+        use ChannelKind::*;
+
+        match self {
+            Self::Rgb | Self::Rgba => [Some(Rgb), Some(Rgb), Some(Rgb), Some(Unit)],
+            Self::Hsv | Self::Hsva | Self::Hsl | Self::Hsla => {
+                [Some(Hue), Some(Unit), Some(Unit), Some(Unit)]
+            }
+            Self::Lab | Self::Oklab => [None, None, None, Some(Unit)],
+            Self::Lch | Self::Oklch => [None, None, Some(Hue), Some(Unit)],
+        }
+    }
+
+    /// Returns the lightness that `100%` stands for in the CSS Color 4 formats, such as `100.0` for `lab()`,
+    /// or [`None`] for the formats that do not have an unbounded lightness channel first.
+    fn lightness_scale(&self) -> Option<f64> {
+        match self {
+            Self::Lab | Self::Lch => Some(100.0),
+            Self::Oklab | Self::Oklch => Some(1.0),
+            _ => None,
+        }
+    }
 }
 
 /// This structure is what CSS color functions will be parsed into.
@@ -93,6 +197,7 @@ pub struct CssColorNotation {
     pub format: CssColorType,
     /// The length of this vector is going to be either three or four, depending on
     /// whether or not the color format has an alpha/transparency channel.
+    /// The CSS Color 4 formats, such as [`CssColorType::Lab`], may have either.
     ///
     /// These values are either going to be a percentage/multiplier,
     /// or a float with an undefined range.
@@ -100,6 +205,116 @@ pub struct CssColorNotation {
     pub values: Vec<CssNumber>,
 }
 
+/// These constructors take the channels in the same ranges as the fields of the matching
+/// color types, such as `0.0..1.0` for the channels of [`crate::Rgb`], and produce the
+/// same notation as converting that color type with [`From`].
+impl CssColorNotation {
+    /// Creates an `rgb()` notation. The channels are ranged `0.0..1.0`.
+    pub fn rgb(r: f64, g: f64, b: f64) -> Self {
+        Self {
+            format: CssColorType::Rgb,
+            values: vec![
+                CssNumber::Float(r * 255.0),
+                CssNumber::Float(g * 255.0),
+                CssNumber::Float(b * 255.0),
+            ],
+        }
+    }
+
+    /// Creates an `rgba()` notation. The channels are ranged `0.0..1.0`.
+    pub fn rgba(r: f64, g: f64, b: f64, alpha: f64) -> Self {
+        Self::with_alpha(CssColorType::Rgba, Self::rgb(r, g, b), alpha)
+    }
+
+    /// Creates an `hsv()` notation. The hue is ranged `0.0..360.0`, the others `0.0..1.0`.
+    pub fn hsv(h: f64, s: f64, v: f64) -> Self {
+        Self {
+            format: CssColorType::Hsv,
+            values: vec![
+                CssNumber::Float(h),
+                CssNumber::Percent(s),
+                CssNumber::Percent(v),
+            ],
+        }
+    }
+
+    /// Creates an `hsva()` notation. The hue is ranged `0.0..360.0`, the others `0.0..1.0`.
+    pub fn hsva(h: f64, s: f64, v: f64, alpha: f64) -> Self {
+        Self::with_alpha(CssColorType::Hsva, Self::hsv(h, s, v), alpha)
+    }
+
+    /// Creates an `hsl()` notation. The hue is ranged `0.0..360.0`, the others `0.0..1.0`.
+    pub fn hsl(h: f64, s: f64, l: f64) -> Self {
+        Self {
+            format: CssColorType::Hsl,
+            values: vec![
+                CssNumber::Float(h),
+                CssNumber::Percent(s),
+                CssNumber::Percent(l),
+            ],
+        }
+    }
+
+    /// Creates an `hsla()` notation. The hue is ranged `0.0..360.0`, the others `0.0..1.0`.
+    pub fn hsla(h: f64, s: f64, l: f64, alpha: f64) -> Self {
+        Self::with_alpha(CssColorType::Hsla, Self::hsl(h, s, l), alpha)
+    }
+
+    /// Creates a `lab()` notation. The lightness is ranged `0.0..100.0`, as in [`crate::Lab`].
+    pub fn lab(l: f64, a: f64, b: f64) -> Self {
+        Self {
+            format: CssColorType::Lab,
+            values: vec![
+                CssNumber::Percent(l / 100.0),
+                CssNumber::Float(a),
+                CssNumber::Float(b),
+            ],
+        }
+    }
+
+    /// Creates an `lch()` notation. The lightness is ranged `0.0..100.0`, as in [`crate::Lch`].
+    pub fn lch(l: f64, c: f64, h: f64) -> Self {
+        Self {
+            format: CssColorType::Lch,
+            values: vec![
+                CssNumber::Percent(l / 100.0),
+                CssNumber::Float(c),
+                CssNumber::Float(h),
+            ],
+        }
+    }
+
+    /// Creates an `oklab()` notation. The lightness is ranged `0.0..1.0`.
+    pub fn oklab(l: f64, a: f64, b: f64) -> Self {
+        Self {
+            format: CssColorType::Oklab,
+            values: vec![
+                CssNumber::Percent(l),
+                CssNumber::Float(a),
+                CssNumber::Float(b),
+            ],
+        }
+    }
+
+    /// Creates an `oklch()` notation. The lightness is ranged `0.0..1.0`.
+    pub fn oklch(l: f64, c: f64, h: f64) -> Self {
+        Self {
+            format: CssColorType::Oklch,
+            values: vec![
+                CssNumber::Percent(l),
+                CssNumber::Float(c),
+                CssNumber::Float(h),
+            ],
+        }
+    }
+
+    fn with_alpha(format: CssColorType, mut notation: Self, alpha: f64) -> Self {
+        notation.format = format;
+        notation.values.push(CssNumber::Percent(alpha));
+        notation
+    }
+}
+
 /// With [`ToString`] and [`std::fmt::Display`], [`float_to_nice_string`] is used internally.
 /// See the documentation for that function to see the representation that you will receive.
 ///
@@ -115,29 +330,135 @@ impl std::fmt::Display for CssNumber {
     }
 }
 
+impl CssNumber {
+    /// Scales this number to the range of a color channel of the kind `kind`,
+    /// and clamps or wraps it into that range. See [`ChannelKind`] for the ranges.
+    pub fn clamp_channel(self, kind: ChannelKind) -> f64 {
+        match (kind, self) {
+            (ChannelKind::Rgb, Self::Float(float)) => (float / 255.0).clamp(0.0, 1.0),
+            (ChannelKind::Hue, Self::Float(degrees)) => degrees.rem_euclid(360.0),
+            (ChannelKind::Hue, Self::Percent(turns)) => (turns * 360.0).rem_euclid(360.0),
+            (ChannelKind::Rgb | ChannelKind::Unit, Self::Percent(float) | Self::Float(float)) => {
+                float.clamp(0.0, 1.0)
+            }
+        }
+    }
+
+    /// Returns `true` if this number is already in the range of a channel of the kind `kind`,
+    /// so that [`CssNumber::clamp_channel`] only scales it. A hue is in range from `0` to `360` degrees,
+    /// or as a percentage from `0%` to `100%` of a turn, even though any other hue would only be wrapped.
+    pub fn is_in_range(self, kind: ChannelKind) -> bool {
+        match (kind, self) {
+            (ChannelKind::Hue, Self::Float(degrees)) => (0.0..=360.0).contains(&degrees),
+            (ChannelKind::Rgb, Self::Float(float)) => (0.0..=255.0).contains(&float),
+            (_, Self::Percent(float) | Self::Float(float)) => (0.0..=1.0).contains(&float),
+        }
+    }
+}
+
+impl CssColorNotation {
+    /// Returns [`Error::ValueOutOfRange`] if any channel would be clamped or wrapped when parsed into a color,
+    /// such as the red channel of `rgb(300, 0, 0)` or the hue of `hsl(400, 50%, 50%)`.
+    /// See [`CssNumber::is_in_range`].
+    ///
+    /// The lightness of the CSS Color 4 formats is not clamped, but it is out of range
+    /// unless it is from `0%` to `100%`, such as in `lab(150% 0 0)`.
+    pub fn check_range(&self) -> Result<()> {
+        let in_range = self
+            .values
+            .iter()
+            .zip(self.format.channel_kinds())
+            .all(|(value, kind)| kind.is_none_or(|kind| value.is_in_range(kind)));
+        let lightness_in_range = match (self.format.lightness_scale(), self.values.first()) {
+            (Some(_), Some(CssNumber::Percent(lightness))) => (0.0..=1.0).contains(lightness),
+            (Some(scale), Some(CssNumber::Float(lightness))) => (0.0..=scale).contains(lightness),
+            _ => true,
+        };
+
+        if in_range && lightness_in_range {
+            Ok(())
+        } else {
+            Err(Error::ValueOutOfRange)
+        }
+    }
+}
+
+/// Angle units are only accepted for hues, see [`CssNumber::from_hue_str`].
 impl FromStr for CssNumber {
     type Err = Error;
 
     fn from_str(string: &str) -> Result<Self> {
-        Ok(if let Some(string) = string.strip_suffix('%') {
-            Self::Percent(string.parse::<f64>().or(Err(Error::InvalidCssPercent))? / 100.0)
+        Self::parse(string, false)
+    }
+}
+
+impl CssNumber {
+    /// Parses a hue in the same way as [`str::parse`], but the number may also have an angle unit,
+    /// which is one of `deg`, `grad`, `rad`, or `turn`. The angle is converted to degrees.
+    pub fn from_hue_str(string: &str) -> Result<Self> {
+        Self::parse(string, true)
+    }
+
+    fn parse(string: &str, is_angle: bool) -> Result<Self> {
+        // Rust's float parsing also accepts `inf` and `NaN`, which are not valid CSS numbers.
+        fn parse_finite(string: &str) -> Option<f64> {
+            string.parse::<f64>().ok().filter(|float| float.is_finite())
+        }
+
+        // Angles are converted to degrees. The `grad` suffix must be checked before `rad`.
+        let (value, degrees_per_unit) = [
+            ("deg", 1.0),
+            ("grad", 0.9),
+            ("rad", 180.0 / std::f64::consts::PI),
+            ("turn", 360.0),
+        ]
+        .into_iter()
+        .filter(|_| is_angle)
+        .find_map(|(unit, scale)| Some((string.strip_suffix(unit)?, scale)))
+        .unwrap_or((string, 1.0));
+
+        Ok(if let Some(number) = value.strip_suffix('%') {
+            let percent = parse_finite(number).ok_or_else(|| Error::InvalidCssPercent {
+                index: 0,
+                found: string.to_owned(),
+            })?;
+
+            Self::Percent(percent / 100.0)
         } else {
-            Self::Float(string.parse().or(Err(Error::InvalidCssFloat))?)
+            let float = parse_finite(value).ok_or_else(|| Error::InvalidCssFloat {
+                index: 0,
+                found: string.to_owned(),
+            })?;
+
+            Self::Float(float * degrees_per_unit)
         })
     }
 }
 
+/// The CSS Color 4 formats, such as [`CssColorType::Lab`], are written with the modern
+/// space-separated syntax, with the alpha channel (if any) after a slash.
+/// The others are written with the legacy comma-separated syntax.
 impl std::fmt::Display for CssColorNotation {
     fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_fmt(format_args!(
-            "{}({})",
-            self.format,
-            self.values
-                .iter()
-                .map(ToString::to_string)
-                .collect::<Vec<_>>()
-                .join(", ")
-        ))
+        let values = self
+            .values
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        if self.format.is_modern() {
+            let (channels, alpha) = values.split_at(values.len().min(3));
+
+            formatter.write_fmt(format_args!("{}({}", self.format, channels.join(" ")))?;
+
+            if let Some(alpha) = alpha.first() {
+                formatter.write_fmt(format_args!(" / {}", alpha))?;
+            }
+
+            formatter.write_str(")")
+        } else {
+            formatter.write_fmt(format_args!("{}({})", self.format, values.join(", ")))
+        }
     }
 }
 
@@ -145,22 +466,63 @@ impl FromStr for CssColorNotation {
     type Err = Error;
 
     fn from_str(string: &str) -> Result<Self> {
-        let string = string.replace(' ', "");
-        let (format, mut values) = string.split_once('(').ok_or(Error::MissingCssParens)?;
-        values = values.strip_suffix(')').ok_or(Error::MissingCssParens)?;
-
-        let format = CssColorType::from_str(format).or(Err(Error::UnknownCssFormat))?;
-        let values = values
-            .split(',')
-            .map(CssNumber::from_str)
-            .collect::<Result<Vec<_>>>()?;
-
-        if values.len()
-            != match format {
-                CssColorType::Rgb | CssColorType::Hsv | CssColorType::Hsl => 3,
-                CssColorType::Rgba | CssColorType::Hsva | CssColorType::Hsla => 4,
+        // Errors point at the part of `string` that failed, which every part here is a slice of.
+        let offset = |part: &str| part.as_ptr() as usize - string.as_ptr() as usize;
+
+        let (format, values) = string
+            .trim()
+            .split_once('(')
+            .ok_or(Error::MissingCssParens)?;
+        let values = values.strip_suffix(')').ok_or(Error::MissingCssParens)?;
+
+        let format = format.trim();
+        let format = CssColorType::from_str(format).map_err(|_| Error::UnknownCssFormat {
+            index: offset(format),
+            found: format.to_owned(),
+        })?;
+        let parse_number = |(index, value): (usize, &str)| {
+            let is_hue = format.channel_kinds().get(index) == Some(&Some(ChannelKind::Hue));
+            let number = match is_hue {
+                true => CssNumber::from_hue_str(value),
+                false => CssNumber::from_str(value),
+            };
+
+            number.map_err(|error| error.offset_by(offset(value)))
+        };
+        let values = if values.contains(',') {
+            values
+                .split(',')
+                .map(str::trim)
+                .enumerate()
+                .map(parse_number)
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            // The CSS Color 4 syntax separates channels with spaces, and the alpha with a slash.
+            let (channels, alpha) = match values.split_once('/') {
+                Some((channels, alpha)) => (channels, Some(alpha.trim())),
+                None => (values, None),
+            };
+
+            let values = channels
+                .split_whitespace()
+                .chain(alpha)
+                .enumerate()
+                .map(parse_number)
+                .collect::<Result<Vec<_>>>()?;
+
+            // There are always three channels before the slash.
+            if alpha.is_some() && values.len() != 4 {
+                return Err(Error::InvalidCssParams);
             }
-        {
+
+            values
+        };
+
+        // As in CSS Color 4, the formats without an `a` suffix may still have an alpha channel.
+        if !match format {
+            CssColorType::Rgba | CssColorType::Hsva | CssColorType::Hsla => values.len() == 4,
+            _ => values.len() == 3 || values.len() == 4,
+        } {
             Err(Error::InvalidCssParams)
         } else {
             Ok(Self { format, values })
@@ -176,20 +538,89 @@ pub fn float_to_nice_string(float: f64) -> String {
     string
 }
 
-pub(crate) fn css_number_to_rgb_channel(number: &CssNumber) -> f64 {
-    match *number {
-        CssNumber::Percent(percent) => percent,
-        CssNumber::Float(float) => float / 255.0,
+/// Parses a list of `name: color` pairs, such as from a theme file, into a map from each name to its color.
+/// Entries are separated by semicolons or newlines, and empty entries are ignored.
+/// Each color is parsed with [`Color::new`], so any notation it accepts can be used.
+///
+/// If a color fails to parse, the returned [`Error::InvalidNamedColor`] includes the name of the entry.
+/// If a name appears more than once, the last color is kept.
+///
+/// ```
+/// use sorbet_color::{css, Rgba};
+///
+/// let theme = css::parse_named_map("primary: #ff0000; accent: rgba(0, 0, 255, 50%)").unwrap();
+///
+/// assert_eq!(theme["primary"], Rgba::from([1.0, 0.0, 0.0, 1.0]));
+/// ```
+pub fn parse_named_map(input: &str) -> Result<HashMap<String, Rgba>> {
+    input
+        .split([';', '\n'])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, color) = entry
+                .split_once(':')
+                .ok_or_else(|| Error::MissingNameSeparator(entry.to_owned()))?;
+            let name = name.trim().to_owned();
+
+            match Rgba::new(color.trim()) {
+                Ok(color) => Ok((name, color)),
+                Err(error) => Err(Error::InvalidNamedColor {
+                    name,
+                    source: Box::new(error),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Splits a list of colors on commas and newlines, skipping the commas inside of parentheses
+/// so that functional notations stay whole. The items are trimmed, and empty items are removed.
+pub(crate) fn split_color_list(input: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let (mut depth, mut start) = (0_usize, 0);
+
+    for (index, char) in input.char_indices() {
+        match char {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(&input[start..index]);
+                start = index + 1;
+            }
+            '\n' => {
+                items.push(&input[start..index]);
+                start = index + 1;
+                depth = 0;
+            }
+            _ => {}
+        }
     }
+    items.push(&input[start..]);
+
+    items
+        .into_iter()
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect()
 }
 
-pub(crate) fn css_number_to_float(number: &CssNumber) -> f64 {
+/// For the CSS Color 4 formats, where each channel defines the value that `100%` refers to.
+pub(crate) fn css_number_to_scaled(number: &CssNumber, reference: f64) -> f64 {
     match *number {
-        CssNumber::Percent(percent) => percent,
+        CssNumber::Percent(percent) => percent * reference,
         CssNumber::Float(float) => float,
     }
 }
 
+/// The alpha channel of the CSS Color 4 formats, which is optional and defaults to opaque.
+pub(crate) fn css_optional_alpha(notation: &CssColorNotation) -> f64 {
+    notation
+        .values
+        .get(3)
+        .map_or(1.0, |alpha| alpha.clamp_channel(ChannelKind::Unit))
+}
+
 #[cfg(test)]
 mod tests {
     use once_cell::sync::Lazy;
@@ -224,10 +655,77 @@ mod tests {
     #[test_case("99.5%" => CssNumber::Percent(0.995))]
     // Ignored because this causes a rounding error and that is inconsequential
     #[test_case("99.9%" => ignore CssNumber::Percent(0.999))]
+    // Demonstrates that a leading zero is not required, as in minified CSS
+    #[test_case(".5" => CssNumber::Float(0.5))]
+    #[test_case(".5%" => CssNumber::Percent(0.005))]
+    // Demonstrates that scientific notation is accepted
+    #[test_case("1e2" => CssNumber::Float(100.0))]
+    #[test_case("2.5E-1" => CssNumber::Float(0.25))]
+    #[test_case("5e1%" => CssNumber::Percent(0.5))]
     fn test_parse_css_number(string: &str) -> CssNumber {
         string.parse::<CssNumber>().unwrap()
     }
 
+    // Demonstrates that angles are converted to degrees
+    #[test_case("90deg" => CssNumber::Float(90.0))]
+    #[test_case("100grad" => CssNumber::Float(90.0))]
+    #[test_case("0.5turn" => CssNumber::Float(180.0))]
+    #[test_case("210" => CssNumber::Float(210.0))]
+    fn test_parse_css_hue(string: &str) -> CssNumber {
+        CssNumber::from_hue_str(string).unwrap()
+    }
+
+    #[test_case("rgb(255deg, 0, 0)" => (4, "255deg".to_owned()) ; "rgb channel")]
+    #[test_case("hsl(0, 50turn, 50%)" => (7, "50turn".to_owned()) ; "saturation")]
+    #[test_case("oklch(70% 0.1rad 240)" => (10, "0.1rad".to_owned()) ; "chroma")]
+    fn test_angle_outside_of_hue(string: &str) -> (usize, String) {
+        match string.parse::<CssColorNotation>().unwrap_err() {
+            Error::InvalidCssFloat { index, found } => (index, found),
+            error => panic!("{error:?} is not an invalid number"),
+        }
+    }
+
+    #[test]
+    fn test_angle_in_hue() {
+        let hsl = "hsl(0.5turn, 50%, 50%)"
+            .parse::<CssColorNotation>()
+            .unwrap();
+        let oklch = "oklch(70% 0.1 100grad)"
+            .parse::<CssColorNotation>()
+            .unwrap();
+
+        assert_eq!(hsl.values[0], CssNumber::Float(180.0));
+        assert_eq!(oklch.values[2], CssNumber::Float(90.0));
+        assert!("90deg".parse::<CssNumber>().is_err());
+    }
+
+    #[test_case(CssNumber::Float(127.5), ChannelKind::Rgb => 0.5 ; "rgb float")]
+    #[test_case(CssNumber::Percent(0.5), ChannelKind::Rgb => 0.5 ; "rgb percent")]
+    #[test_case(CssNumber::Float(300.0), ChannelKind::Rgb => 1.0 ; "rgb clamped")]
+    #[test_case(CssNumber::Float(210.0), ChannelKind::Hue => 210.0 ; "hue degrees")]
+    #[test_case(CssNumber::Percent(0.25), ChannelKind::Hue => 90.0 ; "hue percent")]
+    #[test_case(CssNumber::Float(-30.0), ChannelKind::Hue => 330.0 ; "hue negative")]
+    #[test_case(CssNumber::Float(720.0), ChannelKind::Hue => 0.0 ; "hue two turns")]
+    #[test_case(CssNumber::Float(0.4), ChannelKind::Unit => 0.4 ; "unit float")]
+    #[test_case(CssNumber::Percent(0.4), ChannelKind::Unit => 0.4 ; "unit percent")]
+    #[test_case(CssNumber::Percent(1.5), ChannelKind::Unit => 1.0 ; "unit clamped high")]
+    #[test_case(CssNumber::Float(-0.5), ChannelKind::Unit => 0.0 ; "unit clamped low")]
+    fn test_clamp_channel(number: CssNumber, kind: ChannelKind) -> f64 {
+        number.clamp_channel(kind)
+    }
+
+    // Demonstrates that malformed exponents and non-finite values are rejected
+    #[test_case("1e" => matches Err(Error::InvalidCssFloat { .. }))]
+    #[test_case("1e+" => matches Err(Error::InvalidCssFloat { .. }))]
+    #[test_case("e2" => matches Err(Error::InvalidCssFloat { .. }))]
+    #[test_case("1e999" => matches Err(Error::InvalidCssFloat { .. }))]
+    #[test_case("inf" => matches Err(Error::InvalidCssFloat { .. }))]
+    #[test_case("NaN" => matches Err(Error::InvalidCssFloat { .. }))]
+    #[test_case("1e%" => matches Err(Error::InvalidCssPercent { .. }))]
+    fn test_parse_css_number_invalid(string: &str) -> Result<CssNumber> {
+        string.parse::<CssNumber>()
+    }
+
     // Repeat tests from [`test_float_to_nice_string`]
     #[test_case(&CssNumber::Float(99.0) => "99")]
     #[test_case(&CssNumber::Float(99.9) => "99.9")]
@@ -336,6 +834,56 @@ mod tests {
                     ],
                 },
             ),
+            (
+                // 7: LAB with the space-separated syntax
+                "lab(50% 40 -20.5)",
+                CssColorNotation {
+                    format: CssColorType::Lab,
+                    values: vec![
+                        CssNumber::Percent(0.5),
+                        CssNumber::Float(40.0),
+                        CssNumber::Float(-20.5),
+                    ],
+                },
+            ),
+            (
+                // 8: LCH with alpha after a slash
+                "lch(50% 30 120 / 0.5)",
+                CssColorNotation {
+                    format: CssColorType::Lch,
+                    values: vec![
+                        CssNumber::Percent(0.5),
+                        CssNumber::Float(30.0),
+                        CssNumber::Float(120.0),
+                        CssNumber::Float(0.5),
+                    ],
+                },
+            ),
+            (
+                // 9: OKLAB with small fractional channels
+                "oklab(62.5% 0.1 -0.05)",
+                CssColorNotation {
+                    format: CssColorType::Oklab,
+                    values: vec![
+                        CssNumber::Percent(0.625),
+                        CssNumber::Float(0.1),
+                        CssNumber::Float(-0.05),
+                    ],
+                },
+            ),
+            (
+                // 10: OKLCH with alpha as a percent value
+                "oklch(70% 0.125 240 / 50%)",
+                CssColorNotation {
+                    format: CssColorType::Oklch,
+                    values: vec![
+                        CssNumber::Percent(0.7),
+                        CssNumber::Float(0.125),
+                        CssNumber::Float(240.0),
+                        CssNumber::Percent(0.5),
+                    ],
+                },
+            ),
         ]
     });
 
@@ -347,6 +895,10 @@ mod tests {
     #[test_case(CSS_COLOR_NOTATIONS[4].0 => CSS_COLOR_NOTATIONS[4].1)]
     #[test_case(CSS_COLOR_NOTATIONS[5].0 => CSS_COLOR_NOTATIONS[5].1)]
     #[test_case(CSS_COLOR_NOTATIONS[6].0 => CSS_COLOR_NOTATIONS[6].1)]
+    #[test_case(CSS_COLOR_NOTATIONS[7].0 => CSS_COLOR_NOTATIONS[7].1)]
+    #[test_case(CSS_COLOR_NOTATIONS[8].0 => CSS_COLOR_NOTATIONS[8].1)]
+    #[test_case(CSS_COLOR_NOTATIONS[9].0 => CSS_COLOR_NOTATIONS[9].1)]
+    #[test_case(CSS_COLOR_NOTATIONS[10].0 => CSS_COLOR_NOTATIONS[10].1)]
     fn test_parse_css_color_notation(string: &str) -> CssColorNotation {
         string.parse::<CssColorNotation>().unwrap()
     }
@@ -359,7 +911,111 @@ mod tests {
     #[test_case(&CSS_COLOR_NOTATIONS[4].1 => CSS_COLOR_NOTATIONS[4].0)]
     #[test_case(&CSS_COLOR_NOTATIONS[5].1 => CSS_COLOR_NOTATIONS[5].0)]
     #[test_case(&CSS_COLOR_NOTATIONS[6].1 => CSS_COLOR_NOTATIONS[6].0)]
+    #[test_case(&CSS_COLOR_NOTATIONS[7].1 => CSS_COLOR_NOTATIONS[7].0)]
+    #[test_case(&CSS_COLOR_NOTATIONS[8].1 => CSS_COLOR_NOTATIONS[8].0)]
+    #[test_case(&CSS_COLOR_NOTATIONS[9].1 => CSS_COLOR_NOTATIONS[9].0)]
+    #[test_case(&CSS_COLOR_NOTATIONS[10].1 => CSS_COLOR_NOTATIONS[10].0)]
     fn test_display_css_color_notation(color: &CssColorNotation) -> String {
         color.to_string()
     }
+
+    #[test_case(CssColorNotation::rgb(1.0, 0.5, 0.0) => "rgb(255, 127.5, 0)" ; "rgb")]
+    #[test_case(CssColorNotation::rgba(1.0, 0.0, 0.0, 0.5) => "rgba(255, 0, 0, 50%)" ; "rgba")]
+    #[test_case(CssColorNotation::hsl(210.0, 0.5, 0.4) => "hsl(210, 50%, 40%)" ; "hsl")]
+    #[test_case(CssColorNotation::hsla(210.0, 0.5, 0.4, 0.8) => "hsla(210, 50%, 40%, 80%)" ; "hsla")]
+    #[test_case(CssColorNotation::hsva(90.0, 1.0, 1.0, 1.0) => "hsva(90, 100%, 100%, 100%)" ; "hsva")]
+    #[test_case(CssColorNotation::lab(50.0, 40.0, -20.5) => "lab(50% 40 -20.5)" ; "lab")]
+    #[test_case(CssColorNotation::oklch(0.7, 0.125, 240.0) => "oklch(70% 0.125 240)" ; "oklch")]
+    fn test_constructors(color: CssColorNotation) -> String {
+        color.to_string()
+    }
+
+    // Round-trips each of the CSS Color 4 functions through its color type
+    #[test_case("lab(50% 40 -20.5)" ; "lab")]
+    #[test_case("lch(50% 30 120)" ; "lch")]
+    #[test_case("oklab(62.5% 0.1 -0.05)" ; "oklab")]
+    #[test_case("oklch(70% 0.125 240)" ; "oklch")]
+    fn test_css_color_4_round_trip(string: &str) {
+        let round_trip = match string.parse::<CssColorNotation>().unwrap().format {
+            CssColorType::Lab => string.parse::<crate::Lab>().unwrap().to_string(),
+            CssColorType::Lch => string.parse::<crate::Lch>().unwrap().to_string(),
+            CssColorType::Oklab => string.parse::<crate::Oklab>().unwrap().to_string(),
+            CssColorType::Oklch => string.parse::<crate::Oklch>().unwrap().to_string(),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(round_trip, string);
+    }
+
+    // Demonstrates that the legacy and the space-separated syntaxes are both accepted,
+    // and that the formats without an `a` suffix may have an alpha channel
+    #[test_case("rgb(255, 0, 0)" => [1.0, 0.0, 0.0, 1.0] ; "legacy")]
+    #[test_case("rgb(255 0 0)" => [1.0, 0.0, 0.0, 1.0] ; "modern")]
+    #[test_case("rgb(255 0 0 / 0.5)" => [1.0, 0.0, 0.0, 0.5] ; "modern with alpha")]
+    #[test_case("rgb(100% 0% 0% / 50%)" => [1.0, 0.0, 0.0, 0.5] ; "modern percent")]
+    #[test_case("rgba(255 0 0 / 0.5)" => [1.0, 0.0, 0.0, 0.5] ; "modern rgba")]
+    #[test_case("rgb(255, 0, 0, 0.5)" => [1.0, 0.0, 0.0, 0.5] ; "legacy with alpha")]
+    fn test_parse_rgb_syntaxes(string: &str) -> [f64; 4] {
+        Rgba::new(string).unwrap().into()
+    }
+
+    #[test_case("rgb(255, 1e, 0)" => (9, "1e".to_owned()) ; "legacy float")]
+    #[test_case("  hsl(120 50% x%)" => (14, "x%".to_owned()) ; "modern percent")]
+    #[test_case("oklch(70% 0.1 240 / nope)" => (20, "nope".to_owned()) ; "modern alpha")]
+    #[test_case(" rbg(0, 0, 0)" => (1, "rbg".to_owned()) ; "unknown format")]
+    #[test_case("rgb(10, xdeg, 0)" => (8, "xdeg".to_owned()) ; "angle unit")]
+    #[test_case("hsl(0.5xturn 50% 50%)" => (4, "0.5xturn".to_owned()) ; "turn unit")]
+    fn test_parse_error_position(string: &str) -> (usize, String) {
+        match string.parse::<CssColorNotation>().unwrap_err() {
+            Error::InvalidCssFloat { index, found }
+            | Error::InvalidCssPercent { index, found }
+            | Error::UnknownCssFormat { index, found } => {
+                assert_eq!(&string[index..index + found.len()], found);
+                (index, found)
+            }
+            error => panic!("{error:?} has no position"),
+        }
+    }
+
+    #[test_case("rgba(255 0 0)" ; "rgba without alpha")]
+    #[test_case("rgb(255 0 / 0.5)" ; "missing channel")]
+    fn test_parse_rgb_syntaxes_invalid(string: &str) {
+        assert!(matches!(
+            string.parse::<CssColorNotation>(),
+            Err(Error::InvalidCssParams)
+        ));
+    }
+
+    #[test]
+    fn test_css_color_4_alpha() {
+        let color = Rgba::new("oklch(100% 0 0 / 25%)").unwrap();
+
+        assert_eq!(color.alpha, 0.25);
+        assert!((color.r - 1.0).abs() < 1e-6, "{color:?}");
+    }
+
+    #[test]
+    fn test_parse_named_map() {
+        let theme = parse_named_map(
+            "
+            primary: #ff0000;
+            accent: rgba(0, 0, 255, 50%)
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(theme.len(), 2);
+        assert_eq!(theme["primary"], Rgba::from([1.0, 0.0, 0.0, 1.0]));
+        assert_eq!(theme["accent"], Rgba::from([0.0, 0.0, 1.0, 0.5]));
+    }
+
+    #[test]
+    fn test_parse_named_map_reports_name() {
+        let error = parse_named_map("primary: #ff0000; accent: hsl(nope)").unwrap_err();
+
+        assert!(
+            matches!(&error, Error::InvalidNamedColor { name, .. } if name == "accent"),
+            "{error:?}"
+        );
+    }
 }
@@ -0,0 +1,233 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! This module contains perceptual color difference metrics.
+
+use thiserror::Error;
+
+use crate::types::{Lab, Rgb};
+
+/// A commonly cited CIEDE2000 difference below which two colors are indistinguishable,
+/// the "just noticeable difference". Intended as a threshold for [`crate::Color::within_delta_e`].
+pub const JUST_NOTICEABLE_DIFFERENCE: f64 = 2.3;
+
+/// `25.0.powi(7)`, which appears in the chroma compensation terms of CIEDE2000.
+const POW_25_7: f64 = 6_103_515_625.0;
+
+/// Computes the CIEDE2000 color difference between two [`Lab`] colors,
+/// with the parametric weighting factors `kL`, `kC`, and `kH` all set to `1.0`.
+///
+/// A difference of about `1.0` is the smallest that most observers can notice.
+/// The implementation follows Sharma, Wu, and Dalal,
+/// *The CIEDE2000 Color-Difference Formula: Implementation Notes, Supplementary Test Data,
+/// and Mathematical Observations*.
+pub fn delta_e_2000(a: Lab, b: Lab) -> f64 {
+    // Adjust the a* axis to compensate for the poor hue linearity of CIELAB near neutral.
+    let c_bar = (a.a.hypot(a.b) + b.a.hypot(b.b)) / 2.0;
+    let g = 0.5 * (1.0 - (c_bar.powi(7) / (c_bar.powi(7) + POW_25_7)).sqrt());
+
+    let (a1, a2) = ((1.0 + g) * a.a, (1.0 + g) * b.a);
+    let (c1, c2) = (a1.hypot(a.b), a2.hypot(b.b));
+    let (h1, h2) = (hue_angle(a.b, a1), hue_angle(b.b, a2));
+
+    // Differences in lightness, chroma, and hue.
+    let delta_l = b.l - a.l;
+    let delta_c = c2 - c1;
+    let delta_h = match h2 - h1 {
+        _ if c1 * c2 == 0.0 => 0.0,
+        diff if diff > 180.0 => diff - 360.0,
+        diff if diff < -180.0 => diff + 360.0,
+        diff => diff,
+    };
+    let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+    // Means, taking care to average the hue the short way around the circle.
+    let l_bar = (a.l + b.l) / 2.0;
+    let c_bar = (c1 + c2) / 2.0;
+    let h_bar = match () {
+        _ if c1 * c2 == 0.0 => h1 + h2,
+        _ if (h1 - h2).abs() <= 180.0 => (h1 + h2) / 2.0,
+        _ if h1 + h2 < 360.0 => (h1 + h2 + 360.0) / 2.0,
+        _ => (h1 + h2 - 360.0) / 2.0,
+    };
+
+    // Weighting functions.
+    let t = 1.0 - 0.17 * (h_bar - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar).to_radians().cos()
+        + 0.32 * (3.0 * h_bar + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar - 63.0).to_radians().cos();
+    let s_l = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let s_c = 1.0 + 0.045 * c_bar;
+    let s_h = 1.0 + 0.015 * c_bar * t;
+
+    // The rotation term, for the interaction between chroma and hue differences in the blue region.
+    let delta_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+    let r_c = 2.0 * (c_bar.powi(7) / (c_bar.powi(7) + POW_25_7)).sqrt();
+    let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+    let (l, c, h) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+
+    (l * l + c * c + h * h + r_t * c * h).sqrt()
+}
+
+/// Returned by [`difference_map`] when the two buffers are not the same length.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Error)]
+#[error("the buffers to compare had different lengths, {0} and {1}")]
+pub struct LengthMismatch(pub usize, pub usize);
+
+/// The largest and the average values of a list of color differences,
+/// such as the one returned by [`difference_map`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DifferenceSummary {
+    /// The largest difference.
+    pub max: f64,
+    /// The mean of all of the differences.
+    pub mean: f64,
+}
+
+impl DifferenceSummary {
+    /// Summarizes a list of differences. An empty list has a `max` and `mean` of `0.0`.
+    pub fn of(differences: &[f64]) -> Self {
+        if differences.is_empty() {
+            return Self {
+                max: 0.0,
+                mean: 0.0,
+            };
+        }
+
+        Self {
+            max: differences.iter().copied().fold(0.0, f64::max),
+            mean: differences.iter().sum::<f64>() / differences.len() as f64,
+        }
+    }
+}
+
+/// Computes the CIEDE2000 difference between each pair of pixels in two equal-length buffers.
+/// This is useful for checking that a color transform stayed within a tolerance across an image,
+/// see [`DifferenceSummary`] to reduce the result.
+pub fn difference_map(a: &[Rgb], b: &[Rgb]) -> Result<Vec<f64>, LengthMismatch> {
+    if a.len() != b.len() {
+        return Err(LengthMismatch(a.len(), b.len()));
+    }
+
+    Ok(a.iter().zip(b).map(|(a, b)| a.difference(*b)).collect())
+}
+
+/// The hue angle in degrees, ranged `0.0..360.0`, which is `0.0` for neutral colors.
+fn hue_angle(b: f64, a: f64) -> f64 {
+    if a == 0.0 && b == 0.0 {
+        0.0
+    } else {
+        b.atan2(a).to_degrees().rem_euclid(360.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    // Test data from Table 1 of Sharma, Wu, and Dalal
+    #[test_case([50.0000, 2.6772, -79.7751], [50.0000, 0.0000, -82.7485] => 2.0425 ; "pair 1")]
+    #[test_case([50.0000, 3.1571, -77.2803], [50.0000, 0.0000, -82.7485] => 2.8615 ; "pair 2")]
+    #[test_case([50.0000, 2.8361, -74.0200], [50.0000, 0.0000, -82.7485] => 3.4412 ; "pair 3")]
+    #[test_case([50.0000, -1.3802, -84.2814], [50.0000, 0.0000, -82.7485] => 1.0000 ; "pair 4")]
+    #[test_case([50.0000, -1.1848, -84.8006], [50.0000, 0.0000, -82.7485] => 1.0000 ; "pair 5")]
+    #[test_case([50.0000, -0.9009, -85.5211], [50.0000, 0.0000, -82.7485] => 1.0000 ; "pair 6")]
+    #[test_case([50.0000, 0.0000, 0.0000], [50.0000, -1.0000, 2.0000] => 2.3669 ; "pair 7")]
+    #[test_case([50.0000, -1.0000, 2.0000], [50.0000, 0.0000, 0.0000] => 2.3669 ; "pair 8")]
+    #[test_case([50.0000, 2.4900, -0.0010], [50.0000, -2.4900, 0.0009] => 7.1792 ; "pair 9")]
+    #[test_case([50.0000, 2.4900, -0.0010], [50.0000, -2.4900, 0.0010] => 7.1792 ; "pair 10")]
+    #[test_case([50.0000, 2.4900, -0.0010], [50.0000, -2.4900, 0.0011] => 7.2195 ; "pair 11")]
+    #[test_case([50.0000, 2.4900, -0.0010], [50.0000, -2.4900, 0.0012] => 7.2195 ; "pair 12")]
+    #[test_case([50.0000, -0.0010, 2.4900], [50.0000, 0.0009, -2.4900] => 4.8045 ; "pair 13")]
+    #[test_case([50.0000, -0.0010, 2.4900], [50.0000, 0.0010, -2.4900] => 4.8045 ; "pair 14")]
+    #[test_case([50.0000, -0.0010, 2.4900], [50.0000, 0.0011, -2.4900] => 4.7461 ; "pair 15")]
+    #[test_case([50.0000, 2.5000, 0.0000], [50.0000, 0.0000, -2.5000] => 4.3065 ; "pair 16")]
+    #[test_case([50.0000, 2.5000, 0.0000], [73.0000, 25.0000, -18.0000] => 27.1492 ; "pair 17")]
+    #[test_case([50.0000, 2.5000, 0.0000], [61.0000, -5.0000, 29.0000] => 22.8977 ; "pair 18")]
+    #[test_case([50.0000, 2.5000, 0.0000], [56.0000, -27.0000, -3.0000] => 31.9030 ; "pair 19")]
+    #[test_case([50.0000, 2.5000, 0.0000], [58.0000, 24.0000, 15.0000] => 19.4535 ; "pair 20")]
+    #[test_case([50.0000, 2.5000, 0.0000], [50.0000, 3.1736, 0.5854] => 1.0000 ; "pair 21")]
+    #[test_case([50.0000, 2.5000, 0.0000], [50.0000, 3.2972, 0.0000] => 1.0000 ; "pair 22")]
+    #[test_case([50.0000, 2.5000, 0.0000], [50.0000, 1.8634, 0.5757] => 1.0000 ; "pair 23")]
+    #[test_case([50.0000, 2.5000, 0.0000], [50.0000, 3.2592, 0.3350] => 1.0000 ; "pair 24")]
+    #[test_case([60.2574, -34.0099, 36.2677], [60.4626, -34.1751, 39.4387] => 1.2644 ; "pair 25")]
+    #[test_case([63.0109, -31.0961, -5.8663], [62.8187, -29.7946, -4.0864] => 1.2630 ; "pair 26")]
+    #[test_case([61.2901, 3.7196, -5.3901], [61.4292, 2.2480, -4.9620] => 1.8731 ; "pair 27")]
+    #[test_case([35.0831, -44.1164, 3.7933], [35.0232, -40.0716, 1.5901] => 1.8645 ; "pair 28")]
+    #[test_case([22.7233, 20.0904, -46.6940], [23.0331, 14.9730, -42.5619] => 2.0373 ; "pair 29")]
+    #[test_case([36.4612, 47.8580, 18.3852], [36.2715, 50.5065, 21.2231] => 1.4146 ; "pair 30")]
+    #[test_case([90.8027, -2.0831, 1.4410], [91.1528, -1.6435, 0.0447] => 1.4441 ; "pair 31")]
+    #[test_case([90.9257, -0.5406, -0.9208], [88.6381, -0.8985, -0.7239] => 1.5381 ; "pair 32")]
+    #[test_case([6.7747, -0.2908, -2.4247], [5.8714, -0.0985, -2.2286] => 0.6377 ; "pair 33")]
+    #[test_case([2.0776, 0.0795, -1.1350], [0.9033, -0.0636, -0.5514] => 0.9082 ; "pair 34")]
+    fn test_delta_e_2000_sharma(a: [f64; 3], b: [f64; 3]) -> f64 {
+        let delta = delta_e_2000(Lab::from(a), Lab::from(b));
+
+        // The published values are rounded to four decimal places
+        (delta * 10_000.0).round() / 10_000.0
+    }
+
+    #[test]
+    fn test_delta_e_2000_is_symmetric() {
+        let (a, b) = (Lab::from([50.0, 2.5, 0.0]), Lab::from([56.0, -27.0, -3.0]));
+
+        assert!((delta_e_2000(a, b) - delta_e_2000(b, a)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rgb_difference() {
+        let color = Rgb::from([0.2, 0.4, 0.6]);
+
+        assert_eq!(color.difference(color), 0.0);
+        assert!(color.difference(Rgb::from([0.2, 0.4, 0.62])) < 2.0);
+        assert!(Rgb::from([0.0, 0.0, 0.0]).difference(Rgb::from([1.0, 1.0, 1.0])) > 99.0);
+    }
+
+    #[test]
+    fn test_difference_map_identical() {
+        let pixels = [Rgb::from([0.2, 0.4, 0.6]), Rgb::from([1.0, 0.0, 0.0])];
+        let differences = difference_map(&pixels, &pixels).unwrap();
+
+        assert_eq!(differences, [0.0, 0.0]);
+        assert_eq!(
+            DifferenceSummary::of(&differences),
+            DifferenceSummary {
+                max: 0.0,
+                mean: 0.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_difference_map_summary() {
+        let a = [Rgb::from([0.0, 0.0, 0.0]), Rgb::from([0.5, 0.5, 0.5])];
+        let b = [Rgb::from([0.0, 0.0, 0.0]), Rgb::from([0.6, 0.5, 0.5])];
+        let differences = difference_map(&a, &b).unwrap();
+        let summary = DifferenceSummary::of(&differences);
+
+        assert_eq!(summary.max, differences[1]);
+        assert_eq!(summary.mean, differences[1] / 2.0);
+    }
+
+    #[test]
+    fn test_difference_map_length_mismatch() {
+        let pixels = [Rgb::from([0.0, 0.0, 0.0])];
+
+        assert_eq!(difference_map(&pixels, &[]), Err(LengthMismatch(1, 0)));
+    }
+}
@@ -0,0 +1,196 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! This module contains the logic for bringing colors that fall outside of the sRGB gamut
+//! back inside of it. See [`crate::Color::map_to_srgb`] for the entry point.
+
+use crate::types::*;
+
+/// Channels that are outside of `0.0..=1.0` by less than this are considered in-gamut,
+/// to forgive rounding error accumulated by conversions.
+const GAMUT_EPSILON: f64 = 1e-9;
+
+/// The number of bisection steps taken when searching for the largest in-gamut chroma.
+const CHROMA_SEARCH_STEPS: usize = 32;
+
+/// Selects the trade-off that [`crate::Color::map_to_srgb`] makes when a color
+/// has channels that can't be represented in sRGB.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RenderingIntent {
+    /// Clamps each channel independently into `0.0..=1.0`.
+    /// This is the cheapest option, but can noticeably shift the hue of saturated colors.
+    Clip,
+    /// Reduces the chroma in Oklch, keeping the lightness and hue,
+    /// until the color fits inside the gamut.
+    PreserveHue,
+    /// Moves the color in a straight line toward the gray of equal lightness,
+    /// until the color fits inside the gamut.
+    Perceptual,
+}
+
+pub(crate) fn map_to_srgb(color: Rgb, intent: RenderingIntent) -> Rgb {
+    if is_in_gamut(color) {
+        return clip(color);
+    }
+
+    match intent {
+        RenderingIntent::Clip => clip(color),
+        RenderingIntent::PreserveHue => clip(reduce_chroma(color)),
+        RenderingIntent::Perceptual => clip(scale_toward_gray(color)),
+    }
+}
+
+fn is_in_gamut(color: Rgb) -> bool {
+    let range = -GAMUT_EPSILON..=1.0 + GAMUT_EPSILON;
+
+    range.contains(&color.r) && range.contains(&color.g) && range.contains(&color.b)
+}
+
+fn clip(color: Rgb) -> Rgb {
+    Rgb {
+        r: color.r.clamp(0.0, 1.0),
+        g: color.g.clamp(0.0, 1.0),
+        b: color.b.clamp(0.0, 1.0),
+    }
+}
+
+fn reduce_chroma(color: Rgb) -> Rgb {
+    let Oklab { l, a, b } = Oklab::from(color);
+
+    if l >= 1.0 {
+        return Rgb::from([1.0, 1.0, 1.0]);
+    } else if l <= 0.0 {
+        return Rgb::from([0.0, 0.0, 0.0]);
+    }
+
+    // Bisect for the largest fraction of the original chroma that is still in-gamut.
+    let (mut low, mut high) = (0.0, 1.0);
+
+    for _ in 0..CHROMA_SEARCH_STEPS {
+        let mid = (low + high) / 2.0;
+
+        if is_in_gamut(Rgb::from(Oklab::from([l, a * mid, b * mid]))) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Rgb::from(Oklab::from([l, a * low, b * low]))
+}
+
+fn scale_toward_gray(color: Rgb) -> Rgb {
+    let Oklab { l, .. } = Oklab::from(color);
+    let gray = Rgb::from(Oklab::from([l.clamp(0.0, 1.0), 0.0, 0.0]));
+
+    // For each channel, find how far along the line from the gray to the color we can go.
+    let scale = [(color.r, gray.r), (color.g, gray.g), (color.b, gray.b)]
+        .into_iter()
+        .map(|(channel, gray)| match () {
+            _ if channel > 1.0 => (1.0 - gray) / (channel - gray),
+            _ if channel < 0.0 => (0.0 - gray) / (channel - gray),
+            _ => 1.0,
+        })
+        .fold(1.0, f64::min);
+
+    Rgb {
+        r: gray.r + (color.r - gray.r) * scale,
+        g: gray.g + (color.g - gray.g) * scale,
+        b: gray.b + (color.b - gray.b) * scale,
+    }
+}
+
+//
+// Math helpers
+//
+
+/// The sRGB electro-optical transfer function, extended to negative values by symmetry.
+pub(crate) fn srgb_to_linear(channel: f64) -> f64 {
+    let abs = channel.abs();
+    let linear = if abs <= 0.04045 {
+        abs / 12.92
+    } else {
+        ((abs + 0.055) / 1.055).powf(2.4)
+    };
+
+    linear.copysign(channel)
+}
+
+/// The inverse of [`srgb_to_linear`].
+pub(crate) fn linear_to_srgb(channel: f64) -> f64 {
+    let abs = channel.abs();
+    let encoded = if abs <= 0.0031308 {
+        abs * 12.92
+    } else {
+        1.055 * abs.powf(1.0 / 2.4) - 0.055
+    };
+
+    encoded.copysign(channel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    // A saturated "neon" green that is outside of sRGB
+    const NEON: Rgb = Rgb {
+        r: -0.3,
+        g: 1.2,
+        b: 0.4,
+    };
+
+    #[test]
+    fn test_in_gamut_is_unchanged() {
+        let color = Rgb::from([0.2, 0.4, 0.6]);
+
+        for intent in [
+            RenderingIntent::Clip,
+            RenderingIntent::PreserveHue,
+            RenderingIntent::Perceptual,
+        ] {
+            assert_eq!(color.map_to_srgb(intent), color);
+        }
+    }
+
+    #[test]
+    fn test_intents_are_in_gamut() {
+        for intent in [
+            RenderingIntent::Clip,
+            RenderingIntent::PreserveHue,
+            RenderingIntent::Perceptual,
+        ] {
+            assert!(is_in_gamut(NEON.map_to_srgb(intent)), "{intent:?}");
+        }
+    }
+
+    #[test]
+    fn test_clip_and_preserve_hue_differ() {
+        let clipped = NEON.map_to_srgb(RenderingIntent::Clip);
+        let preserved = NEON.map_to_srgb(RenderingIntent::PreserveHue);
+
+        assert_ne!(clipped.rgb_array(), preserved.rgb_array());
+    }
+
+    #[test]
+    fn test_preserve_hue_keeps_oklab_hue() {
+        let Oklab { a: a0, b: b0, .. } = Oklab::from(NEON);
+        let Oklab { a: a1, b: b1, .. } =
+            Oklab::from(NEON.map_to_srgb(RenderingIntent::PreserveHue));
+
+        assert!((b0.atan2(a0) - b1.atan2(a1)).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,147 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! This module contains the [`Gradient`] type, which represents a color ramp
+//! defined by a list of color stops.
+
+use crate::Color;
+
+/// The smallest distance from either end that a midpoint bias may be placed,
+/// to keep the reparameterization exponent finite.
+const MIN_BIAS: f64 = 1e-6;
+
+/// A color gradient, defined by a list of stops that each have a position and a color.
+///
+/// Positions are expected to be in the range `0.0..=1.0`, where `0.0` is the start
+/// of the gradient and `1.0` is the end. The stops are always kept sorted by position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gradient<C: Color> {
+    stops: Vec<(f64, C)>,
+}
+
+impl<C: Color> Gradient<C> {
+    /// Creates a new gradient from a list of `(position, color)` stops.
+    /// The stops do not need to be in order; they will be sorted by position.
+    pub fn new(mut stops: Vec<(f64, C)>) -> Self {
+        stops.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        Self { stops }
+    }
+
+    /// Returns the stops of the gradient, sorted by position.
+    pub fn stops(&self) -> &[(f64, C)] {
+        &self.stops
+    }
+
+    /// Returns the color of the gradient at the position `t`.
+    /// See [`Color::sample_stops`] for how the stops are interpolated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the gradient has no stops.
+    pub fn sample(&self, t: f64) -> C {
+        C::sample_stops(&self.stops, t)
+    }
+
+    /// Returns a new gradient that runs in the opposite direction.
+    /// Each stop at position `p` is moved to `1.0 - p`, and the order of the stops is reversed.
+    #[must_use]
+    pub fn reversed(&self) -> Self {
+        Self {
+            stops: self
+                .stops
+                .iter()
+                .rev()
+                .map(|&(position, color)| (1.0 - position, color))
+                .collect(),
+        }
+    }
+
+    /// Returns a new gradient with the stop positions reparameterized so that whatever was at the
+    /// halfway point is moved to `bias`, like the midpoint handle in a gradient editor.
+    ///
+    /// A `bias` below `0.5` pushes the visual midpoint toward the start, and above `0.5`
+    /// toward the end. A `bias` of exactly `0.5` leaves the gradient unchanged.
+    /// The `bias` is clamped to be just inside of `0.0..1.0`.
+    ///
+    /// Positions are remapped by `p.powf(bias.ln() / 0.5.ln())`, so the endpoints `0.0` and
+    /// `1.0` stay where they are and the order of the stops is preserved.
+    #[must_use]
+    pub fn with_midpoint_bias(&self, bias: f64) -> Self {
+        let bias = bias.clamp(MIN_BIAS, 1.0 - MIN_BIAS);
+        let exponent = bias.ln() / 0.5_f64.ln();
+
+        Self {
+            stops: self
+                .stops
+                .iter()
+                .map(|&(position, color)| (position.clamp(0.0, 1.0).powf(exponent), color))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rgb;
+
+    fn gradient() -> Gradient<Rgb> {
+        Gradient::new(vec![
+            (1.0, Rgb::from([1.0, 1.0, 1.0])),
+            (0.0, Rgb::from([0.0, 0.0, 0.0])),
+            (0.25, Rgb::from([1.0, 0.0, 0.0])),
+        ])
+    }
+
+    #[test]
+    fn test_new_sorts_stops() {
+        let positions = gradient()
+            .stops()
+            .iter()
+            .map(|(position, _)| *position)
+            .collect::<Vec<_>>();
+
+        assert_eq!(positions, [0.0, 0.25, 1.0]);
+    }
+
+    #[test]
+    fn test_reversed() {
+        let reversed = gradient().reversed();
+
+        assert_eq!(reversed.stops()[0], (0.0, Rgb::from([1.0, 1.0, 1.0])));
+        assert_eq!(reversed.stops()[1], (0.75, Rgb::from([1.0, 0.0, 0.0])));
+        assert_eq!(reversed.reversed(), gradient());
+    }
+
+    #[test]
+    fn test_sample_at_stop() {
+        assert_eq!(gradient().sample(0.25), Rgb::from([1.0, 0.0, 0.0]));
+        assert_eq!(gradient().sample(1.0), Rgb::from([1.0, 1.0, 1.0]));
+    }
+
+    #[test]
+    fn test_midpoint_bias_identity() {
+        assert_eq!(gradient().with_midpoint_bias(0.5), gradient());
+    }
+
+    #[test]
+    fn test_midpoint_bias_moves_midpoint() {
+        let biased = Gradient::new(vec![(0.5, Rgb::from([0.5, 0.5, 0.5]))]).with_midpoint_bias(0.8);
+
+        assert!((biased.stops()[0].0 - 0.8).abs() < 1e-12);
+    }
+}
@@ -17,8 +17,14 @@
 #![warn(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+pub mod adaptation;
 pub mod css;
+pub mod distance;
+pub mod gamut;
+pub mod gradient;
+pub mod luminance;
 pub mod named;
+pub mod palette;
 pub mod types;
 
 pub use types::*;
@@ -39,24 +45,36 @@ pub trait Color:
     + From<Hsva>
     + From<Hsl>
     + From<Hsla>
+    + From<Xyz>
+    + From<Oklab>
+    + From<Cmyk>
     + Into<Rgb>
     + Into<Rgba>
     + Into<Hsv>
     + Into<Hsva>
     + Into<Hsl>
     + Into<Hsla>
+    + Into<Xyz>
+    + Into<Oklab>
+    + Into<Cmyk>
 {
     /// This constructor takes a CSS-compatible functional notation for a color, and coerces it to an
     /// explicit or inferred type. This will return [`css::Error`] variants if the parsing fails.
     ///
-    /// Spaces are ignored but other whitespace is not.
+    /// Whitespace around the values is ignored. The values may be separated by commas,
+    /// or by spaces with the alpha after a slash as in CSS Color 4, such as `oklch(70% 0.1 240 / 50%)`.
     /// When providing a hexadecimal color, the `#` prefix is required, whereas the unchecked
-    /// [`From<&str>`] on [`Rgb`] and [`Rgba`] has no such restriction.
+    /// [`TryFrom<&str>`] on [`Rgb`] and [`Rgba`] has no such restriction.
     ///
-    /// ~~Note that if any parameters inside the string are not within a channel's valid range,
-    /// they will be clamped instead of wrapped.~~
-    /// Numbers parsing to values outside the acceptable range for the fields of a color type
-    /// may result in undefined behavior.
+    /// The channels of the `rgb`, `hsv`, and `hsl` notations, and the alpha of every notation,
+    /// are clamped into their valid range, and hues are wrapped (see [`css::ChannelKind`]).
+    /// Use [`Color::parse_strict`] to get an error for those channels instead.
+    /// The other channels of the CSS Color 4 notations, such as `lab`, are not bounded.
+    /// Conversions between color types may still produce channels out of range, so call `clamped`
+    /// on the result, such as [`Hsl::clamped`], to bring every channel back into its range.
+    ///
+    /// Errors about a part of the string, such as [`css::Error::InvalidCssFloat`], have the byte
+    /// offset of that part in `string`. See [`css::Error::index`].
     ///
     /// See the [reference on W3 Schools](https://www.w3schools.com/cssref/css_colors_legal.asp)
     /// for valid input strings. Current supported prefixes match the type names for color structures
@@ -65,10 +83,11 @@ pub trait Color:
     where
         S: AsRef<str>,
     {
-        let string = string.as_ref().replace(' ', "").to_ascii_lowercase();
+        // This is not trimmed, so that the positions in errors are the same as in the input.
+        let string = string.as_ref().to_ascii_lowercase();
 
-        if let Some(string) = string.strip_prefix('#') {
-            Self::from_hex(string)
+        if let Some(string) = string.trim().strip_prefix('#') {
+            Self::from_hex(string.replace(' ', ""))
         } else {
             // Here we don't just parse the string and use the [`Self::TryFrom`] implementation
             // directly because that may use the wrong one and throw an error.
@@ -79,39 +98,117 @@ pub trait Color:
             let interm = string.parse::<css::CssColorNotation>()?;
 
             Ok(match interm.format {
-                css::CssColorType::Rgb => Rgb::try_from(&interm)?.into(),
-                css::CssColorType::Rgba => Rgba::try_from(&interm)?.into(),
-                css::CssColorType::Hsv => Hsv::try_from(&interm)?.into(),
-                css::CssColorType::Hsva => Hsva::try_from(&interm)?.into(),
-                css::CssColorType::Hsl => Hsl::try_from(&interm)?.into(),
-                css::CssColorType::Hsla => Hsla::try_from(&interm)?.into(),
+                // The alpha channel is optional for the formats without an `a` suffix.
+                css::CssColorType::Rgb | css::CssColorType::Rgba => Rgba::try_from(&interm)?.into(),
+                css::CssColorType::Hsv | css::CssColorType::Hsva => Hsva::try_from(&interm)?.into(),
+                css::CssColorType::Hsl | css::CssColorType::Hsla => Hsla::try_from(&interm)?.into(),
+                // These formats have an optional alpha channel, but the types do not.
+                css::CssColorType::Lab => Rgba {
+                    alpha: css::css_optional_alpha(&interm),
+                    ..Lab::try_from(&interm)?.into()
+                }
+                .into(),
+                css::CssColorType::Lch => Rgba {
+                    alpha: css::css_optional_alpha(&interm),
+                    ..Lch::try_from(&interm)?.into()
+                }
+                .into(),
+                css::CssColorType::Oklab => Rgba {
+                    alpha: css::css_optional_alpha(&interm),
+                    ..Oklab::try_from(&interm)?.into()
+                }
+                .into(),
+                css::CssColorType::Oklch => Rgba {
+                    alpha: css::css_optional_alpha(&interm),
+                    ..Oklch::try_from(&interm)?.into()
+                }
+                .into(),
             })
         }
     }
 
+    /// Parses a color in the same way as [`Color::new`], but returns [`css::Error::ValueOutOfRange`]
+    /// instead of clamping a channel that is out of range, such as the red channel of `rgb(300, 0, 0)`.
+    /// This is for validators and linters that should point out such colors rather than fix them.
+    ///
+    /// Hues outside of `0..=360` degrees are rejected as well, rather than wrapped,
+    /// and so are lightnesses outside of `0%..=100%` in the CSS Color 4 notations.
+    /// See [`css::CssColorNotation::check_range`].
+    fn parse_strict<S>(string: S) -> css::Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        let string = string.as_ref();
+        let lowercase = string.trim().to_ascii_lowercase();
+
+        if !lowercase.starts_with('#') {
+            lowercase.parse::<css::CssColorNotation>()?.check_range()?;
+        }
+
+        Self::new(string)
+    }
+
+    /// The same as [`Color::parse_strict`], named to sit beside [`Color::new`] for input validation.
+    fn new_strict<S>(string: S) -> css::Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        Self::parse_strict(string)
+    }
+
+    /// Parses a color in the same way as [`Color::new`], but returns `default` instead of an error
+    /// if the string could not be parsed. This is convenient when loading user-provided colors
+    /// that have a known fallback, such as from a configuration file.
+    fn parse_or<S>(string: S, default: Self) -> Self
+    where
+        S: AsRef<str>,
+    {
+        Self::new(string).unwrap_or(default)
+    }
+
+    /// Parses a list of colors separated by commas and/or newlines, such as a palette file,
+    /// with [`Color::new`]. Commas inside of a functional notation such as `rgb(0, 0, 0)`
+    /// do not split it, and empty items are skipped.
+    ///
+    /// Parsing stops at the first color that fails, returning [`css::Error::InvalidListItem`]
+    /// with the index of the color in the list, not counting empty items.
+    fn parse_all<S>(input: S) -> css::Result<Vec<Self>>
+    where
+        S: AsRef<str>,
+    {
+        css::split_color_list(input.as_ref())
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                Self::new(item).map_err(|error| css::Error::InvalidListItem {
+                    index,
+                    source: Box::new(error),
+                })
+            })
+            .collect()
+    }
+
+    /// Similar to [`Color::parse_or`], but the fallback color is lazily computed by calling `default`
+    /// only if the string could not be parsed.
+    fn parse_or_else<S, F>(string: S, default: F) -> Self
+    where
+        S: AsRef<str>,
+        F: FnOnce() -> Self,
+    {
+        Self::new(string).unwrap_or_else(|_| default())
+    }
+
     /// Takes a hexadecimal-encoded RGB or RGBA string,
     /// and coerces to an explicit or inferred color type.
     /// With this constructor the `#` prefix is optional, but this expects no whitespace.
     ///
-    /// If you want an [`Rgb`] or [`Rgba`] type, it is recommended to use either
-    /// `Rgb::from(string)` or `Rgba::from(string)`, which do not return a [`css::Result`]
-    /// and will simply panic on invalid input.
+    /// If you want an [`Rgb`] or [`Rgba`] type, you can also use either
+    /// `Rgb::try_from(string)` or `Rgba::try_from(string)`.
     fn from_hex<S>(string: S) -> css::Result<Self>
     where
         S: AsRef<str>,
     {
-        let mut string = string.as_ref();
-        string = string.strip_prefix('#').unwrap_or(string);
-
-        if !string.bytes().all(|b| b.is_ascii_hexdigit()) {
-            Err(css::Error::InvalidHexChars)
-        } else if string.len() == 6 {
-            Ok(Rgb::from(string).into())
-        } else if string.len() == 8 {
-            Ok(Rgba::from(string).into())
-        } else {
-            Err(css::Error::InvalidHexLength)
-        }
+        Ok(Rgba::try_from(string.as_ref())?.into())
     }
 
     /// This constructor takes an unsigned 32-bit integer and coerces it to an
@@ -149,6 +246,553 @@ pub trait Color:
     fn rgba_array(&self) -> [u8; 4] {
         Into::<Rgba>::into(*self).into()
     }
+
+    /// Returns the red, green, blue, and alpha channels as unsigned 8-bit integers,
+    /// in that order, routing through [`Rgba`].
+    ///
+    /// This is intended as a minimal, stable representation for passing colors
+    /// across an FFI boundary, for example as a C `uint8_t[4]`.
+    /// The byte order is always `[r, g, b, a]` regardless of the platform's endianness,
+    /// unlike [`Color::int`]. This is equivalent to [`Color::rgba_array`].
+    fn rgba_bytes(&self) -> [u8; 4] {
+        self.rgba_array()
+    }
+
+    /// The inverse of [`Color::rgba_bytes`], this takes red, green, blue, and alpha channels
+    /// in that order as unsigned 8-bit integers and coerces them to an explicit or inferred type.
+    fn from_rgba_bytes(bytes: [u8; 4]) -> Self {
+        Rgba::from(bytes).into()
+    }
+
+    /// Returns this color with the alpha channel of `other`, routing both through [`Rgba`].
+    ///
+    /// This is useful for recoloring something while preserving its transparency.
+    /// Types without an alpha channel, such as [`Rgb`], discard it, so for them this is a no-op.
+    #[must_use]
+    fn with_alpha_from(self, other: &impl Color) -> Self {
+        let alpha = Into::<Rgba>::into(*other).alpha;
+
+        Rgba {
+            alpha,
+            ..self.into()
+        }
+        .into()
+    }
+
+    /// Mixes this color with `other`, where `t` of `0.0` is this color and `1.0` is `other`,
+    /// such as `a.mix(b, 0.5)` for the average of the two. This is the method form of [`lerp`],
+    /// so the mix happens in [`Rgba`] and `t` is clamped to `0.0..=1.0`.
+    #[must_use]
+    fn mix(self, other: Self, t: f64) -> Self {
+        lerp(self, other, t)
+    }
+
+    /// Returns the even average of all of `colors`, such as for blending a selection of swatches.
+    /// The colors are averaged in [`Oklab`] like [`Color::sample_stops`], and the alpha channel linearly.
+    /// See [`Color::blend_even_in`] to average in another color space.
+    ///
+    /// A single color is returned as-is, and if `colors` is empty this returns [`None`].
+    fn blend_even(colors: &[Self]) -> Option<Self> {
+        Self::blend_even_in(colors, BlendSpace::Oklab)
+    }
+
+    /// Similar to [`Color::blend_even`], but the colors are averaged in the color space `space`.
+    fn blend_even_in(colors: &[Self], space: BlendSpace) -> Option<Self> {
+        match colors {
+            [] => return None,
+            [color] => return Some(*color),
+            _ => (),
+        }
+
+        let mut sum = [0.0; 4];
+
+        for &color in colors {
+            let color = Into::<Rgba>::into(color);
+            let [x, y, z] = space.components(color.into());
+
+            for (total, channel) in sum.iter_mut().zip([x, y, z, color.alpha]) {
+                *total += channel;
+            }
+        }
+
+        let [x, y, z, alpha] = sum.map(|total| total / colors.len() as f64);
+
+        Some(
+            Rgba {
+                alpha,
+                ..space.to_rgb([x, y, z]).into()
+            }
+            .into(),
+        )
+    }
+
+    /// Returns `true` if the CIEDE2000 difference between this color and `other` is
+    /// below `threshold`. See [`Rgb::difference`] and [`distance::JUST_NOTICEABLE_DIFFERENCE`].
+    fn within_delta_e(&self, other: &Self, threshold: f64) -> bool {
+        Into::<Rgb>::into(*self).difference((*other).into()) < threshold
+    }
+
+    /// Returns the name of the closest color in [`named::NAMED_COLORS`], and its CIEDE2000
+    /// difference from this color, such as for labelling a color as "almost red".
+    /// See [`named::nearest_name`] and [`Rgb::difference`].
+    fn nearest_named(&self) -> (&'static str, f64) {
+        let rgb = Into::<Rgb>::into(*self);
+        let name = named::nearest_name(rgb);
+        let named = Rgb::from(named::from_name(name).unwrap());
+
+        (name, rgb.difference(named))
+    }
+
+    /// Returns the contrast ratio of this color as text on the background `bg`,
+    /// and whether it passes each of the WCAG 2.1 levels. See [`luminance::ContrastReport`].
+    fn contrast_report(&self, bg: Rgb) -> luminance::ContrastReport {
+        luminance::ContrastReport::new((*self).into(), bg)
+    }
+
+    /// Returns the lowest contrast ratio of this color as text over any of `backgrounds`,
+    /// such as samples of an image or the stops of a gradient that the text is drawn over.
+    /// Text that passes a WCAG level against this ratio passes it everywhere on the backdrop.
+    /// See [`luminance::contrast_ratio`].
+    ///
+    /// If `backgrounds` is empty, this returns [`f64::INFINITY`].
+    fn min_contrast_against(&self, backgrounds: &[Rgb]) -> f64 {
+        let text = (*self).into();
+
+        backgrounds
+            .iter()
+            .map(|&background| luminance::contrast_ratio(text, background))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Interpolates the hue and chroma from this color toward `other` in [`Oklch`] like
+    /// [`mix_oklch`], but keeps the lightness of this color for the whole mix,
+    /// so that the result does not change in perceived brightness as `t` moves.
+    ///
+    /// The mixed color may be outside of the sRGB gamut, in which case the channels of
+    /// the returned [`Rgb`] are outside of `0.0..1.0`. See [`gamut`] to bring it back.
+    fn mix_iso_luminance(self, other: Self, t: f64) -> Rgb {
+        let a = Oklch::from(Into::<Oklab>::into(self));
+        let b = Oklch::from(Into::<Oklab>::into(other));
+
+        Oklch {
+            l: a.l,
+            ..mix_oklch(a, b, t)
+        }
+        .into()
+    }
+
+    /// Returns this color, or a variation of it that is at least `min_delta_e` from every color in
+    /// `existing` by CIEDE2000 (see [`Rgb::difference`]), such as when adding a color to a chart
+    /// that must be told apart from the colors already in it.
+    ///
+    /// If this color is too close to one of `existing`, its hue is rotated in [`Oklch`], keeping
+    /// the lightness and chroma, in steps of `10` degrees until it is far enough from all of them.
+    /// The rotated colors are brought into sRGB with [`gamut::RenderingIntent::PreserveHue`].
+    /// If no hue clears the threshold, the one furthest from its nearest existing color is returned.
+    fn ensure_distinct(self, existing: &[Rgb], min_delta_e: f64) -> Rgb {
+        const HUE_STEP: f64 = 10.0;
+
+        let origin = Oklch::from(Into::<Oklab>::into(self));
+        let nearest = |color: Rgb| {
+            existing
+                .iter()
+                .map(|other| color.difference(*other))
+                .fold(f64::INFINITY, f64::min)
+        };
+
+        let mut best = (f64::NEG_INFINITY, Into::<Rgb>::into(self));
+
+        for step in 0..(360.0 / HUE_STEP) as usize {
+            let candidate = Oklch {
+                h: (origin.h + step as f64 * HUE_STEP).rem_euclid(360.0),
+                ..origin
+            };
+            let candidate =
+                gamut::map_to_srgb(candidate.into(), gamut::RenderingIntent::PreserveHue);
+            let distance = nearest(candidate);
+
+            if distance >= min_delta_e {
+                return candidate;
+            }
+            if distance > best.0 {
+                best = (distance, candidate);
+            }
+        }
+
+        best.1
+    }
+
+    /// Returns the color as HSL for showing to people, such as in a tooltip,
+    /// with every channel rounded to an integer and suffixed with its unit: `hsl(210°, 50%, 40%)`.
+    ///
+    /// This is not valid CSS because of the degree sign. See [`Hsl::to_css`] for that.
+    fn to_hsl_display(&self) -> String {
+        let Hsl { h, s, l } = (*self).into();
+
+        format!(
+            "hsl({}°, {}%, {}%)",
+            round_hue(h),
+            (s * 100.0).round(),
+            (l * 100.0).round()
+        )
+    }
+
+    /// Returns the color in CSS `hsl()` notation with the syntax `syntax`, with the alpha
+    /// channel only if the color is not opaque. The legacy syntax uses `hsla()` for that.
+    /// Unlike [`Color::to_hsl_display`], this is valid CSS and can be parsed by [`Color::new`].
+    fn to_css_hsl(&self, syntax: css::CssSyntax) -> String {
+        let Hsla { h, s, l, alpha } = (*self).into();
+        let h = css::float_to_nice_string(h);
+        let s = css::float_to_nice_string(s * 100.0);
+        let l = css::float_to_nice_string(l * 100.0);
+        let alpha = css::float_to_nice_string(alpha);
+
+        match (syntax, alpha.as_str()) {
+            (css::CssSyntax::Legacy, "1") => format!("hsl({h}, {s}%, {l}%)"),
+            (css::CssSyntax::Legacy, _) => format!("hsla({h}, {s}%, {l}%, {alpha})"),
+            (css::CssSyntax::Modern, "1") => format!("hsl({h} {s}% {l}%)"),
+            (css::CssSyntax::Modern, _) => format!("hsl({h} {s}% {l}% / {alpha})"),
+        }
+    }
+
+    /// The HSV counterpart of [`Color::to_hsl_display`], such as `hsv(210°, 75%, 60%)`.
+    fn to_hsv_display(&self) -> String {
+        let Hsv { h, s, v } = (*self).into();
+
+        format!(
+            "hsv({}°, {}%, {}%)",
+            round_hue(h),
+            (s * 100.0).round(),
+            (v * 100.0).round()
+        )
+    }
+
+    /// Returns a quantized integer that increases monotonically with the relative luminance
+    /// of the color. See [`Rgb::relative_luminance`].
+    ///
+    /// This is intended as a cheap key for sorting large lists of colors by brightness,
+    /// for example with [`slice::sort_by_key`] or a radix sort, without recomputing the luminance
+    /// for every comparison.
+    ///
+    /// The key is lossy: luminance is clamped to `0.0..=1.0` and scaled to the full range of
+    /// [`u32`], so colors with nearly identical luminance may share the same key.
+    fn luminance_key(&self) -> u32 {
+        let luminance = Into::<Rgb>::into(*self).relative_luminance();
+
+        (luminance.clamp(0.0, 1.0) * u32::MAX as f64).round() as u32
+    }
+
+    /// Returns the color as linear-light red, green, blue, and alpha channels,
+    /// ready to be uploaded as a shader uniform.
+    ///
+    /// The color channels are decoded with the sRGB transfer function,
+    /// while the alpha channel is passed through as-is.
+    fn to_linear_rgba_f32(&self) -> [f32; 4] {
+        let Rgba { r, g, b, alpha } = (*self).into();
+        let [r, g, b] = [r, g, b].map(gamut::srgb_to_linear);
+
+        [r as f32, g as f32, b as f32, alpha as f32]
+    }
+
+    /// The inverse of [`Color::to_linear_rgba_f32`], this takes linear-light red, green, blue,
+    /// and alpha channels, encodes the color channels with the sRGB transfer function,
+    /// and coerces the result to an explicit or inferred type.
+    fn from_linear_rgba_f32(linear: [f32; 4]) -> Self {
+        let [r, g, b, alpha] = linear.map(f64::from);
+        let [r, g, b] = [r, g, b].map(gamut::linear_to_srgb);
+
+        Rgba { r, g, b, alpha }.into()
+    }
+
+    /// Brings a color with channels outside of the sRGB gamut back inside of it,
+    /// making the trade-off selected by `intent`. See [`gamut::RenderingIntent`] for the options.
+    ///
+    /// Colors that are already inside of the gamut are returned unchanged.
+    fn map_to_srgb(&self, intent: gamut::RenderingIntent) -> Rgb {
+        gamut::map_to_srgb(Into::<Rgb>::into(*self), intent)
+    }
+
+    /// Samples the piecewise gradient defined by a list of `(position, color)` stops at the position `t`.
+    /// This is what [`gradient::Gradient::sample`] uses, but it avoids constructing a gradient for
+    /// one-off sampling.
+    ///
+    /// The two stops on either side of `t` are interpolated in [`Oklab`], which gives perceptually
+    /// even transitions, and the alpha channel is interpolated linearly. If `t` is outside of the
+    /// range of the stops, the color of the nearest stop is returned.
+    /// See [`Color::sample_stops_in`] to interpolate in another color space.
+    ///
+    /// The stops must be sorted by position, which is only checked in debug builds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stops` is empty.
+    fn sample_stops(stops: &[(f64, Self)], t: f64) -> Self {
+        Self::sample_stops_in(stops, t, BlendSpace::Oklab)
+    }
+
+    /// Similar to [`Color::sample_stops`], but the stops are interpolated in the color space `space`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stops` is empty.
+    fn sample_stops_in(stops: &[(f64, Self)], t: f64, space: BlendSpace) -> Self {
+        debug_assert!(
+            stops.windows(2).all(|pair| pair[0].0 <= pair[1].0),
+            "the stops must be sorted by position"
+        );
+        assert!(
+            !stops.is_empty(),
+            "there must be at least one stop to sample"
+        );
+
+        // The index of the first stop that is after `t`.
+        let index = stops.partition_point(|&(position, _)| position <= t);
+
+        if index == 0 {
+            return stops[0].1;
+        } else if index == stops.len() {
+            return stops[index - 1].1;
+        }
+
+        let ((start, from), (end, to)) = (stops[index - 1], stops[index]);
+
+        // Avoid the round trip through `space` when sampling exactly on a stop.
+        if t == start {
+            return from;
+        }
+
+        let t = (t - start) / (end - start);
+        let lerp = |from: f64, to: f64| from + (to - from) * t;
+
+        let (from, to): (Rgba, Rgba) = (from.into(), to.into());
+        let (from_components, to_components) =
+            (space.components(from.into()), space.components(to.into()));
+        let mut mixed = [0.0; 3];
+
+        for (channel, (from, to)) in mixed
+            .iter_mut()
+            .zip(from_components.into_iter().zip(to_components))
+        {
+            *channel = lerp(from, to);
+        }
+
+        Rgba {
+            alpha: lerp(from.alpha, to.alpha),
+            ..space.to_rgb(mixed).into()
+        }
+        .into()
+    }
+
+    /// Returns the color in the CSS Color 4 `color(display-p3 r g b / a)` notation,
+    /// with the channels converted into the [`DisplayP3`] color space.
+    /// This is how wide-gamut colors are authored in modern CSS.
+    fn to_color_p3_css(&self) -> String {
+        let color: Rgba = (*self).into();
+        let DisplayP3 { r, g, b } = DisplayP3::from(color);
+
+        format!(
+            "color(display-p3 {} {} {} / {})",
+            css::float_to_nice_string(r),
+            css::float_to_nice_string(g),
+            css::float_to_nice_string(b),
+            css::float_to_nice_string(color.alpha),
+        )
+    }
+
+    /// Renders a solid `width` by `height` tile of the color, and returns the encoded PNG bytes.
+    /// Translucent colors are composited over a gray and white checkerboard,
+    /// so that the transparency is still visible in the swatch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `height` is zero, because PNG images can not be empty.
+    #[cfg(feature = "image")]
+    fn to_swatch_png(&self, width: u32, height: u32) -> Vec<u8> {
+        use image::{codecs::png::PngEncoder, ColorType, ImageEncoder, RgbImage};
+
+        // The size of each checkerboard square in pixels.
+        const CHECKER_SIZE: u32 = 8;
+
+        let color: Rgba = (*self).into();
+        let image = RgbImage::from_fn(width, height, |x, y| {
+            let checker = match (x / CHECKER_SIZE + y / CHECKER_SIZE) % 2 {
+                0 => 1.0,
+                _ => 0.8,
+            };
+            let (rgb, _) =
+                Rgb::composite_parts(color, Rgba::from([checker, checker, checker, 1.0]));
+
+            image::Rgb(rgb.into())
+        });
+
+        let mut bytes = Vec::new();
+
+        PngEncoder::new(&mut bytes)
+            .write_image(image.as_raw(), width, height, ColorType::Rgb8)
+            .expect("failed to encode the swatch");
+
+        bytes
+    }
+}
+
+/// Parses a color from whatever a user may have typed, trying each of these in order
+/// and returning the first success:
+///
+/// 1. Hexadecimal notation, with a `#` prefix, a `0x` prefix, or neither.
+///    The three and four-digit shorthands such as `#f00` are expanded.
+/// 2. CSS functional notation, as accepted by [`Color::new`].
+/// 3. A name from [`named::NAMED_COLORS`], ignoring case.
+///
+/// If all of them fail, [`css::Error::UnrecognizedColor`] is returned with the errors
+/// from the first two attempts.
+///
+/// ```
+/// use sorbet_color::{parse_any, AnyColor, Rgb};
+///
+/// assert_eq!(parse_any("Red").unwrap(), AnyColor::Rgb(Rgb::from([1.0, 0.0, 0.0])));
+/// assert!(matches!(parse_any("hsl(0, 100%, 50%)"), Ok(AnyColor::Hsl(_))));
+/// ```
+pub fn parse_any(string: &str) -> css::Result<AnyColor> {
+    let string = string.trim();
+
+    let hex = parse_any_hex(string);
+    if let Ok(color) = hex {
+        return Ok(color);
+    }
+
+    let functional = string
+        .to_ascii_lowercase()
+        .parse::<css::CssColorNotation>()
+        .and_then(|notation| AnyColor::try_from(&notation));
+    if let Ok(color) = functional {
+        return Ok(color);
+    }
+
+    match named::from_name(string) {
+        Some(int) => Ok(AnyColor::Rgb(int.into())),
+        None => Err(css::Error::UnrecognizedColor {
+            input: string.to_owned(),
+            hex: Box::new(hex.unwrap_err()),
+            functional: Box::new(functional.unwrap_err()),
+        }),
+    }
+}
+
+fn parse_any_hex(string: &str) -> css::Result<AnyColor> {
+    let digits = string
+        .strip_prefix('#')
+        .or_else(|| string.strip_prefix("0x"))
+        .unwrap_or(string);
+    // Expand the shorthand forms by doubling each digit
+    let digits = match digits.len() {
+        3 | 4 => digits.chars().flat_map(|digit| [digit, digit]).collect(),
+        _ => digits.to_owned(),
+    };
+
+    match digits.len() {
+        8 => Ok(AnyColor::Rgba(Rgba::try_from(digits.as_str())?)),
+        _ => Ok(AnyColor::Rgb(Rgb::try_from(digits.as_str())?)),
+    }
+}
+
+/// Selects the color space that colors are blended in, such as by [`Color::sample_stops_in`]
+/// and [`Color::blend_even_in`]. All of these are rectangular, so that the channels can be
+/// interpolated and averaged independently.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlendSpace {
+    /// Blends in [`Oklab`], which is close to perceptually uniform.
+    #[default]
+    Oklab,
+    /// Blends in CIE [`Lab`], which is perceptual but less uniform than [`Oklab`] for saturated blues.
+    Lab,
+    /// Blends the gamma-encoded channels of [`Rgb`], which is cheap but darkens the middle of a blend.
+    Srgb,
+    /// Blends the linear-light channels of [`Rgb`], as light mixes physically.
+    LinearRgb,
+}
+
+impl BlendSpace {
+    fn components(self, color: Rgb) -> [f64; 3] {
+        match self {
+            Self::Oklab => Oklab::from(color).into(),
+            Self::Lab => Lab::from(color).into(),
+            Self::Srgb => color.into(),
+            Self::LinearRgb => <[f64; 3]>::from(color).map(gamut::srgb_to_linear),
+        }
+    }
+
+    fn to_rgb(self, components: [f64; 3]) -> Rgb {
+        match self {
+            Self::Oklab => Oklab::from(components).into(),
+            Self::Lab => Lab::from(components).into(),
+            Self::Srgb => components.into(),
+            Self::LinearRgb => components.map(gamut::linear_to_srgb).into(),
+        }
+    }
+}
+
+/// Linearly interpolates between `a` and `b` in the gamma-encoded [`Rgba`] color space,
+/// including the alpha channel, and coerces the result back to the type of the endpoints.
+///
+/// The factor `t` is clamped to `0.0..=1.0`, where `0.0` is `a` and `1.0` is `b`.
+/// For perceptually even transitions, see [`Color::sample_stops`] instead.
+pub fn lerp<C: Color>(a: C, b: C, t: f64) -> C {
+    let t = t.clamp(0.0, 1.0);
+    let a: [f64; 4] = Into::<Rgba>::into(a).into();
+    let b: [f64; 4] = Into::<Rgba>::into(b).into();
+    let mut mixed = [0.0; 4];
+
+    for (channel, (a, b)) in mixed.iter_mut().zip(a.into_iter().zip(b)) {
+        *channel = a + (b - a) * t;
+    }
+
+    Rgba::from(mixed).into()
+}
+
+/// Returns `steps` evenly-spaced colors from `a` to `b` inclusive, interpolated with [`lerp`].
+///
+/// The first and last colors are exactly `a` and `b`, without a round-trip through [`Rgba`].
+/// If `steps` is `1` only `a` is returned, and if it is `0` the result is empty.
+pub fn gradient<C: Color>(a: C, b: C, steps: usize) -> Vec<C> {
+    ramp(a, b, steps, lerp)
+}
+
+/// Similar to [`gradient()`], but the colors are interpolated in [`Oklch`] with [`mix_oklch`]
+/// for a perceptually even ramp. The alpha channel is interpolated linearly.
+pub fn gradient_oklch<C: Color>(a: C, b: C, steps: usize) -> Vec<C> {
+    ramp(a, b, steps, |a, b, t| {
+        let (a, b): (Rgba, Rgba) = (a.into(), b.into());
+        let mixed = Rgba::from(mix_oklch(Oklch::from(a), Oklch::from(b), t));
+
+        Rgba {
+            alpha: a.alpha + (b.alpha - a.alpha) * t,
+            ..mixed
+        }
+        .into()
+    })
+}
+
+/// Rounds a hue to the nearest degree, wrapping `360` to `0`.
+fn round_hue(hue: f64) -> f64 {
+    hue.round().rem_euclid(360.0)
+}
+
+fn ramp<C: Color>(a: C, b: C, steps: usize, mix: impl Fn(C, C, f64) -> C) -> Vec<C> {
+    match steps {
+        0 => Vec::new(),
+        1 => vec![a],
+        _ => {
+            let last = steps - 1;
+
+            (0..steps)
+                .map(|step| match step {
+                    0 => a,
+                    _ if step == last => b,
+                    _ => mix(a, b, step as f64 / last as f64),
+                })
+                .collect()
+        }
+    }
 }
 
 macro_rules! impl_from_str_css {
@@ -190,33 +834,65 @@ macro_rules! impl_display_css {
     };
 }
 
-impl_from_str_css!(Rgb, Rgba, Hsv, Hsva, Hsl, Hsla);
-impl_display_css!(Rgb, Rgba, Hsv, Hsva, Hsl, Hsla);
+/// Defines a tuple struct wrapping one of the color types from this crate, that still behaves
+/// like a color. This is useful for giving distinct types to colors with different roles,
+/// such as a foreground and a background, so that they can't be mixed up by accident.
+///
+/// The generated type derives the same traits as the wrapped type, implements [`Color`]
+/// and [`std::fmt::Display`] by forwarding to the wrapped value, and implements
+/// [`From`] to and from every color type that [`Color`] requires.
+///
+/// ```
+/// use sorbet_color::{color_newtype, Color, Rgba};
+///
+/// color_newtype! {
+///     /// The color used to draw text.
+///     pub Foreground(Rgba)
+/// }
+///
+/// let text = Foreground::from_int(sorbet_color::named::BLACK);
+/// assert_eq!(text.hex(), "#000000FF");
+/// ```
+#[macro_export]
+macro_rules! color_newtype {
+    ( $( #[$meta:meta] )* $vis:vis $name:ident($inner:ty) ) => {
+        $( #[$meta] )*
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        $vis struct $name(pub $inner);
 
-// #[cfg(test)]
-// mod tests {
-//     use super::Color;
-//     use once_cell::sync::Lazy;
+        impl $crate::Color for $name {
+            fn hex(&self) -> String {
+                $crate::Color::hex(&self.0)
+            }
+        }
 
-//     static TEST_COLORS: Lazy<Vec<&str>> = Lazy::new(|| {
-//         "#353B48, #666666, #444852, #FCFCFC, #434343, #90939B, #353537, #2B303B, #B6B8C0, #241F31, #303440, #000000, #9398A2, #DFDFDF, #F0F1F2, #CFCFCF, #D3D8E2, #505666, #808080, #8A939F, #282B36, #AFB8C6, #383838, #4DADD4, #353A48, #838383, #202229, #7A7F8A, #7A7F8B, #2E3340, #70788D, #66A1DC, #17191F, #D7D7D7, #545860, #39404D, #161A26, #BE3841, #3C4049, #2F3A42, #F0F2F5, #4E4EFF, #262934, #1D1F26, #404552, #353945, #383C45, #8F939D, #F7EF45, #A4AAB7, #B2CDF1, #444A58, #BAC3CF, #FF00FF, #F46067, #5C6070, #C7CACF, #525762, #FF0B00, #323644, #F75A61, #464646, #ECEDF0, #171717, #E01B24, #1B1B1B, #797D87, #15171C, #8C919D, #4D4F52, #5B627B, #728495, #454C5C, #4080FB, #E2E2E2, #D1D3DA, #C0E3FF, #3580E4, #B7C0D3, #232428, #2D323F, #6E6E6E, #DCDCDC, #B9BCC2, #CC575D, #A1A1A1, #52555E, #353A47, #7C818C, #979DAC, #2F343F, #DDE3E9, #828282, #C5DCF7, #001AFF, #722563, #AFB8C5, #222529, #8ABFDD, #666A74, #F68086, #EDF5FB, #4B5162, #A9ACB2, #786613, #C7C7C7, #EEEFF1, #2B2E37, #F70505, #292C36, #3E434F, #5C616C, #F57900, #2D303B, #F5F6F7, #5F697F, #2E3436, #808791, #F08437, #CBD2E3, #E5A50A, #EEEEEE, #252932, #E7E8EB, #3E4350, #FF1111, #EF2929, #FC4138, #FCFDFD, #7A7A7A, #21242B, #BEBEBE, #FFFFFF, #252A35, #5252FF, #767B87, #535353, #3E3E3E, #AA5555, #5F6578, #C4C7CC, #383C4A, #102B68, #21252B, #F3AF0B, #CFD6E6, #D7787D, #FF7A80, #FDFDFD, #398DD3, #A51D2D, #73D216, #F8F8F9, #262932, #2F343B, #2B2E39, #2D3036, #F04A50, #006098, #3F4453, #AD4242, #1B1C21, #B9BFCE, #FF1616, #E5E5E5, #ED686F, #EAEBED, #FBFCFC, #398CD3, #262933, #5294E2, #0000FF, #D7D8DD, #2B2F3B, #F13039, #999999, #1F1F1F, #50DBB5, #525252, #FF2121, #F27835, #91949C, #ADAFB5, #3B3C3E, #D3D4D8, #525D76, #434652, #CACACA, #2D323D, #F9FAFB, #617C95, #EDEDED, #1A1A1A, #D8354A, #90949E, #313541, #A8A8A8, #DBDFE3, #CECECE, #0F0F0F, #1D242A, #B8BABF, #0F1116, #EEF4FC, #E2E7EF, #D3DAE3".split(", ").collect()
-//     });
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                ::std::fmt::Display::fmt(&self.0, formatter)
+            }
+        }
 
-//     #[test]
-//     fn test_to_from_hsv() {
-//         for color in TEST_COLORS.iter() {
-//             let mutated = &Color::new(color).to_hsva().to_hex();
+        $crate::color_newtype!(@from $name, $inner; Rgb, Rgba, Hsv, Hsva, Hsl, Hsla, Xyz, Oklab, Cmyk);
+    };
+    ( @from $name:ident, $inner:ty; $( $t:ident ),+ ) => {
+        $(
+            impl From<$crate::$t> for $name {
+                fn from(other: $crate::$t) -> Self {
+                    Self(<$inner>::from(other))
+                }
+            }
 
-//             assert_eq!(mutated, color);
-//         }
-//     }
+            impl From<$name> for $crate::$t {
+                fn from(other: $name) -> Self {
+                    Self::from(other.0)
+                }
+            }
+        )+
+    };
+}
 
-//     #[test]
-//     fn test_to_from_hsl() {
-//         for color in TEST_COLORS.iter() {
-//             let mutated = &Color::new(color).to_hsla().to_hex();
+impl_from_str_css!(Rgb, Rgba, Hsv, Hsva, Hsl, Hsla, Lab, Lch, Oklab, Oklch);
+impl_display_css!(Rgb, Rgba, Hsv, Hsva, Hsl, Hsla, Lab, Lch, Oklab, Oklch);
 
-//             assert_eq!(mutated, color);
-//         }
-//     }
-// }
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,230 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! This module contains helpers built on the relative luminance of colors.
+//! See [`Rgb::relative_luminance`] for the definition.
+
+use std::cmp::Ordering;
+
+use crate::types::Rgb;
+
+/// The minimum contrast ratio for normal text to pass WCAG 2.1 level AA.
+pub const WCAG_AA_NORMAL_TEXT: f64 = 4.5;
+
+/// The minimum contrast ratio for large text to pass WCAG 2.1 level AA.
+/// Large text is at least 18 point, or 14 point and bold.
+pub const WCAG_AA_LARGE_TEXT: f64 = 3.0;
+
+/// The minimum contrast ratio for normal text to pass WCAG 2.1 level AAA.
+pub const WCAG_AAA_NORMAL_TEXT: f64 = 7.0;
+
+/// The minimum contrast ratio for large text to pass WCAG 2.1 level AAA.
+pub const WCAG_AAA_LARGE_TEXT: f64 = 4.5;
+
+/// Returns the [WCAG 2.1 contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio)
+/// between two colors, ranged `1.0..=21.0`. The order of the colors does not matter.
+pub fn contrast_ratio(a: Rgb, b: Rgb) -> f64 {
+    let (a, b) = (a.relative_luminance(), b.relative_luminance());
+    let (lighter, darker) = (a.max(b), a.min(b));
+
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Returns `true` if text in one color on a background of the other is legible enough
+/// to pass WCAG 2.1 level AA. See [`WCAG_AA_NORMAL_TEXT`] and [`WCAG_AA_LARGE_TEXT`].
+pub fn meets_wcag_aa(a: Rgb, b: Rgb, large_text: bool) -> bool {
+    let threshold = if large_text {
+        WCAG_AA_LARGE_TEXT
+    } else {
+        WCAG_AA_NORMAL_TEXT
+    };
+
+    contrast_ratio(a, b) >= threshold
+}
+
+/// The contrast ratio between two colors, and whether it passes each of the WCAG 2.1 levels.
+/// This has everything needed for a row of an accessibility audit.
+/// See [`crate::Color::contrast_report`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ContrastReport {
+    /// The contrast ratio, see [`contrast_ratio`].
+    pub ratio: f64,
+    /// Passes level AA for normal text, see [`WCAG_AA_NORMAL_TEXT`].
+    pub aa_normal: bool,
+    /// Passes level AA for large text, see [`WCAG_AA_LARGE_TEXT`].
+    pub aa_large: bool,
+    /// Passes level AAA for normal text, see [`WCAG_AAA_NORMAL_TEXT`].
+    pub aaa_normal: bool,
+    /// Passes level AAA for large text, see [`WCAG_AAA_LARGE_TEXT`].
+    pub aaa_large: bool,
+}
+
+impl ContrastReport {
+    /// Computes the report for text in one color on a background of the other.
+    /// The order of the colors does not matter.
+    pub fn new(a: Rgb, b: Rgb) -> Self {
+        let ratio = contrast_ratio(a, b);
+
+        Self {
+            ratio,
+            aa_normal: ratio >= WCAG_AA_NORMAL_TEXT,
+            aa_large: ratio >= WCAG_AA_LARGE_TEXT,
+            aaa_normal: ratio >= WCAG_AAA_NORMAL_TEXT,
+            aaa_large: ratio >= WCAG_AAA_LARGE_TEXT,
+        }
+    }
+}
+
+/// An [`Rgb`] color paired with its relative luminance, which is computed once on construction.
+///
+/// This is ordered by luminance (ties are broken by the color channels), so a large palette can be
+/// sorted by brightness, or collected into a [`std::collections::BTreeSet`], without repeating
+/// the sRGB linearization for every comparison.
+#[derive(Copy, Clone, Debug)]
+pub struct LumColor {
+    color: Rgb,
+    luminance: f64,
+}
+
+impl LumColor {
+    /// Wraps `color`, computing its relative luminance.
+    pub fn new(color: Rgb) -> Self {
+        Self {
+            color,
+            luminance: color.relative_luminance(),
+        }
+    }
+
+    /// Returns the wrapped color.
+    pub fn color(&self) -> Rgb {
+        self.color
+    }
+
+    /// Returns the relative luminance of the wrapped color.
+    pub fn luminance(&self) -> f64 {
+        self.luminance
+    }
+}
+
+impl From<Rgb> for LumColor {
+    fn from(color: Rgb) -> Self {
+        Self::new(color)
+    }
+}
+
+impl From<LumColor> for Rgb {
+    fn from(other: LumColor) -> Self {
+        other.color
+    }
+}
+
+impl Ord for LumColor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.luminance
+            .total_cmp(&other.luminance)
+            .then_with(|| self.color.r.total_cmp(&other.color.r))
+            .then_with(|| self.color.g.total_cmp(&other.color.g))
+            .then_with(|| self.color.b.total_cmp(&other.color.b))
+    }
+}
+
+impl PartialOrd for LumColor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for LumColor {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for LumColor {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio_extremes() {
+        let (black, white) = (Rgb::from([0.0, 0.0, 0.0]), Rgb::from([1.0, 1.0, 1.0]));
+
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-12);
+        assert_eq!(contrast_ratio(white, black), contrast_ratio(black, white));
+        assert_eq!(contrast_ratio(white, white), 1.0);
+    }
+
+    // Gray #767676 is the lightest gray that passes AA on white, and #777777 does not
+    #[test_case([0x76; 3], false => true ; "lightest passing gray")]
+    #[test_case([0x77; 3], false => false ; "darkest failing gray")]
+    #[test_case([0x77; 3], true => true ; "large text")]
+    #[test_case([0x95; 3], true => false ; "large text too light")]
+    fn test_meets_wcag_aa(gray: [u8; 3], large_text: bool) -> bool {
+        meets_wcag_aa(Rgb::from(gray), Rgb::from([1.0, 1.0, 1.0]), large_text)
+    }
+
+    #[test]
+    fn test_contrast_report_black_on_white() {
+        let report = ContrastReport::new(Rgb::from([0.0, 0.0, 0.0]), Rgb::from([1.0, 1.0, 1.0]));
+
+        assert!(report.aa_normal && report.aa_large && report.aaa_normal && report.aaa_large);
+    }
+
+    // Gray #767676 on white has a ratio of about 4.54
+    #[test]
+    fn test_contrast_report_low_contrast() {
+        let report = ContrastReport::new(Rgb::from([0x76_u8; 3]), Rgb::from([1.0, 1.0, 1.0]));
+
+        assert!(
+            (report.ratio - 4.54).abs() < 0.01,
+            "ratio was {}",
+            report.ratio
+        );
+        assert!(report.aa_normal && report.aa_large && report.aaa_large);
+        assert!(!report.aaa_normal);
+    }
+
+    #[test]
+    fn test_caches_luminance() {
+        let color = Rgb::from([0.2, 0.4, 0.6]);
+
+        assert_eq!(LumColor::new(color).luminance(), color.relative_luminance());
+    }
+
+    #[test]
+    fn test_ordered_by_luminance() {
+        let palette = [
+            Rgb::from([1.0, 1.0, 1.0]),
+            Rgb::from([0.0, 0.0, 1.0]),
+            Rgb::from([0.0, 1.0, 0.0]),
+            Rgb::from([0.0, 0.0, 0.0]),
+        ];
+        let sorted = palette
+            .into_iter()
+            .map(LumColor::from)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(Rgb::from)
+            .collect::<Vec<_>>();
+
+        assert_eq!(sorted, [palette[3], palette[1], palette[2], palette[0]]);
+    }
+}
@@ -11,6 +11,11 @@
 //! To get a color structure from it use the [`From<u32>`] implementation.
 //! Currently only [`crate::types::Rgb`] and [`crate::types::Rgba`] implement this.
 //! For a constructor that can coerce to an explicit or implicit type, see [`crate::Color::from_int`].
+//!
+//! To look up a color by a name only known at runtime, see [`from_name`].
+//! To go the other way, from a color to its name, see [`nearest_name`] and [`exact_name`].
+
+use crate::types::Rgb;
 
 pub const ALICEBLUE: u32 = 0xF0F8FFFF;
 pub const ANTIQUEWHITE: u32 = 0xFAEBD7FF;
@@ -160,3 +165,226 @@ pub const WHITE: u32 = 0xFFFFFFFF;
 pub const WHITESMOKE: u32 = 0xF5F5F5FF;
 pub const YELLOW: u32 = 0xFFFF00FF;
 pub const YELLOWGREEN: u32 = 0x9ACD32FF;
+
+/// Every named color in this module, paired with its lowercase CSS name and sorted by name.
+/// Some colors have more than one name, such as [`GRAY`] and [`GREY`].
+pub const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", ALICEBLUE),
+    ("antiquewhite", ANTIQUEWHITE),
+    ("aqua", AQUA),
+    ("aquamarine", AQUAMARINE),
+    ("azure", AZURE),
+    ("beige", BEIGE),
+    ("bisque", BISQUE),
+    ("black", BLACK),
+    ("blanchedalmond", BLANCHEDALMOND),
+    ("blue", BLUE),
+    ("blueviolet", BLUEVIOLET),
+    ("brown", BROWN),
+    ("burlywood", BURLYWOOD),
+    ("cadetblue", CADETBLUE),
+    ("chartreuse", CHARTREUSE),
+    ("chocolate", CHOCOLATE),
+    ("coral", CORAL),
+    ("cornflowerblue", CORNFLOWERBLUE),
+    ("cornsilk", CORNSILK),
+    ("crimson", CRIMSON),
+    ("cyan", CYAN),
+    ("darkblue", DARKBLUE),
+    ("darkcyan", DARKCYAN),
+    ("darkgoldenrod", DARKGOLDENROD),
+    ("darkgray", DARKGRAY),
+    ("darkgreen", DARKGREEN),
+    ("darkgrey", DARKGREY),
+    ("darkkhaki", DARKKHAKI),
+    ("darkmagenta", DARKMAGENTA),
+    ("darkolivegreen", DARKOLIVEGREEN),
+    ("darkorange", DARKORANGE),
+    ("darkorchid", DARKORCHID),
+    ("darkred", DARKRED),
+    ("darksalmon", DARKSALMON),
+    ("darkseagreen", DARKSEAGREEN),
+    ("darkslateblue", DARKSLATEBLUE),
+    ("darkslategray", DARKSLATEGRAY),
+    ("darkslategrey", DARKSLATEGREY),
+    ("darkturquoise", DARKTURQUOISE),
+    ("darkviolet", DARKVIOLET),
+    ("deeppink", DEEPPINK),
+    ("deepskyblue", DEEPSKYBLUE),
+    ("dimgray", DIMGRAY),
+    ("dimgrey", DIMGREY),
+    ("dodgerblue", DODGERBLUE),
+    ("firebrick", FIREBRICK),
+    ("floralwhite", FLORALWHITE),
+    ("forestgreen", FORESTGREEN),
+    ("fuchsia", FUCHSIA),
+    ("gainsboro", GAINSBORO),
+    ("ghostwhite", GHOSTWHITE),
+    ("gold", GOLD),
+    ("goldenrod", GOLDENROD),
+    ("gray", GRAY),
+    ("green", GREEN),
+    ("greenyellow", GREENYELLOW),
+    ("grey", GREY),
+    ("honeydew", HONEYDEW),
+    ("hotpink", HOTPINK),
+    ("indianred", INDIANRED),
+    ("indigo", INDIGO),
+    ("ivory", IVORY),
+    ("khaki", KHAKI),
+    ("lavender", LAVENDER),
+    ("lavenderblush", LAVENDERBLUSH),
+    ("lawngreen", LAWNGREEN),
+    ("lemonchiffon", LEMONCHIFFON),
+    ("lightblue", LIGHTBLUE),
+    ("lightcoral", LIGHTCORAL),
+    ("lightcyan", LIGHTCYAN),
+    ("lightgoldenrodyellow", LIGHTGOLDENRODYELLOW),
+    ("lightgray", LIGHTGRAY),
+    ("lightgreen", LIGHTGREEN),
+    ("lightgrey", LIGHTGREY),
+    ("lightpink", LIGHTPINK),
+    ("lightsalmon", LIGHTSALMON),
+    ("lightseagreen", LIGHTSEAGREEN),
+    ("lightskyblue", LIGHTSKYBLUE),
+    ("lightslategray", LIGHTSLATEGRAY),
+    ("lightslategrey", LIGHTSLATEGREY),
+    ("lightsteelblue", LIGHTSTEELBLUE),
+    ("lightyellow", LIGHTYELLOW),
+    ("lime", LIME),
+    ("limegreen", LIMEGREEN),
+    ("linen", LINEN),
+    ("magenta", MAGENTA),
+    ("maroon", MAROON),
+    ("mediumaquamarine", MEDIUMAQUAMARINE),
+    ("mediumblue", MEDIUMBLUE),
+    ("mediumorchid", MEDIUMORCHID),
+    ("mediumpurple", MEDIUMPURPLE),
+    ("mediumseagreen", MEDIUMSEAGREEN),
+    ("mediumslateblue", MEDIUMSLATEBLUE),
+    ("mediumspringgreen", MEDIUMSPRINGGREEN),
+    ("mediumturquoise", MEDIUMTURQUOISE),
+    ("mediumvioletred", MEDIUMVIOLETRED),
+    ("midnightblue", MIDNIGHTBLUE),
+    ("mintcream", MINTCREAM),
+    ("mistyrose", MISTYROSE),
+    ("moccasin", MOCCASIN),
+    ("navajowhite", NAVAJOWHITE),
+    ("navy", NAVY),
+    ("oldlace", OLDLACE),
+    ("olive", OLIVE),
+    ("olivedrab", OLIVEDRAB),
+    ("orange", ORANGE),
+    ("orangered", ORANGERED),
+    ("orchid", ORCHID),
+    ("palegoldenrod", PALEGOLDENROD),
+    ("palegreen", PALEGREEN),
+    ("paleturquoise", PALETURQUOISE),
+    ("palevioletred", PALEVIOLETRED),
+    ("papayawhip", PAPAYAWHIP),
+    ("peachpuff", PEACHPUFF),
+    ("peru", PERU),
+    ("pink", PINK),
+    ("plum", PLUM),
+    ("powderblue", POWDERBLUE),
+    ("purple", PURPLE),
+    ("rebeccapurple", REBECCAPURPLE),
+    ("red", RED),
+    ("rosybrown", ROSYBROWN),
+    ("royalblue", ROYALBLUE),
+    ("saddlebrown", SADDLEBROWN),
+    ("salmon", SALMON),
+    ("sandybrown", SANDYBROWN),
+    ("seagreen", SEAGREEN),
+    ("seashell", SEASHELL),
+    ("sienna", SIENNA),
+    ("silver", SILVER),
+    ("skyblue", SKYBLUE),
+    ("slateblue", SLATEBLUE),
+    ("slategray", SLATEGRAY),
+    ("slategrey", SLATEGREY),
+    ("snow", SNOW),
+    ("springgreen", SPRINGGREEN),
+    ("steelblue", STEELBLUE),
+    ("tan", TAN),
+    ("teal", TEAL),
+    ("thistle", THISTLE),
+    ("tomato", TOMATO),
+    ("turquoise", TURQUOISE),
+    ("violet", VIOLET),
+    ("wheat", WHEAT),
+    ("white", WHITE),
+    ("whitesmoke", WHITESMOKE),
+    ("yellow", YELLOW),
+    ("yellowgreen", YELLOWGREEN),
+];
+
+/// Returns the color with the given name from [`NAMED_COLORS`], ignoring ASCII case,
+/// or [`None`] if there is no color with that name.
+pub fn from_name(name: &str) -> Option<u32> {
+    let name = name.bytes().map(|byte| byte.to_ascii_lowercase());
+
+    NAMED_COLORS
+        .binary_search_by(|(entry, _)| entry.bytes().cmp(name.clone()))
+        .ok()
+        .map(|index| NAMED_COLORS[index].1)
+}
+
+/// Returns the name of the color in [`NAMED_COLORS`] that is perceptually closest to `color`,
+/// using the CIEDE2000 difference. See [`Rgb::difference`].
+///
+/// When several names share the closest color, the first one alphabetically is returned.
+pub fn nearest_name(color: Rgb) -> &'static str {
+    NAMED_COLORS
+        .iter()
+        .map(|&(name, int)| (name, color.difference(Rgb::from(int))))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(name, _)| name)
+        .unwrap()
+}
+
+/// Returns the name of the color in [`NAMED_COLORS`] that is exactly `color`
+/// when both are quantized to 8-bit channels, or [`None`] if there is no such color.
+///
+/// When several names share the color, the first one alphabetically is returned.
+pub fn exact_name(color: Rgb) -> Option<&'static str> {
+    let rgb: [u8; 3] = color.into();
+
+    NAMED_COLORS
+        .iter()
+        .find(|&&(_, int)| <[u8; 3]>::from(Rgb::from(int)) == rgb)
+        .map(|&(name, _)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case([100, 149, 237] => "cornflowerblue" ; "exact")]
+    #[test_case([101, 150, 235] => "cornflowerblue" ; "near cornflowerblue")]
+    #[test_case([250, 5, 3] => "red" ; "near red")]
+    #[test_case([0, 255, 255] => "aqua" ; "alias")]
+    fn test_nearest_name(rgb: [u8; 3]) -> &'static str {
+        nearest_name(Rgb::from(rgb))
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(from_name("CornflowerBlue"), Some(CORNFLOWERBLUE));
+        assert_eq!(from_name("yellowgreen"), Some(YELLOWGREEN));
+        assert_eq!(from_name("aliceblue"), Some(ALICEBLUE));
+        assert_eq!(from_name("notacolor"), None);
+    }
+
+    #[test]
+    fn test_exact_name() {
+        assert_eq!(exact_name(Rgb::from([128_u8, 128, 128])), Some("gray"));
+        assert_eq!(exact_name(Rgb::from([129_u8, 128, 128])), None);
+    }
+
+    #[test]
+    fn test_named_colors_sorted() {
+        assert!(NAMED_COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
@@ -0,0 +1,314 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! This module contains [`Palette`], an ordered collection of colors such as a swatch grid,
+//! and [`index_buffer`] for mapping an image onto a palette for indexed color.
+
+use thiserror::Error;
+
+use crate::types::{Oklab, Rgb};
+
+/// The largest number of colors in a palette for [`index_buffer`], so that every index fits in a byte.
+pub const MAX_INDEXED_COLORS: usize = 256;
+
+/// Returned by [`index_buffer`] when the pixels can not be indexed into the palette.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Error)]
+pub enum IndexBufferError {
+    /// The palette had no colors, so there was nothing for the pixels to index.
+    #[error("the palette had no colors, but there were pixels to index")]
+    Empty,
+    /// The palette had more than [`MAX_INDEXED_COLORS`] colors, so the indices would not fit in a byte.
+    #[error("the palette had {0} colors, but indexed color allows at most {max}", max = MAX_INDEXED_COLORS)]
+    TooLarge(usize),
+}
+
+/// An ordered list of colors, such as the swatches of a color picker or the colors of a theme.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Palette {
+    /// The colors in the palette, in order.
+    pub colors: Vec<Rgb>,
+}
+
+impl Palette {
+    /// Returns the palette reordered so that neighboring colors look alike, which gives the smooth
+    /// "rainbow" ordering that palette tools use for swatch grids.
+    ///
+    /// This is a greedy nearest-neighbor heuristic for the shortest path through all of the colors,
+    /// starting from the darkest one and repeatedly stepping to the remaining color with the smallest
+    /// CIEDE2000 difference (see [`Rgb::difference`]). It is not guaranteed to find the shortest path,
+    /// and takes quadratic time in the number of colors.
+    pub fn sorted_perceptual(&self) -> Palette {
+        let mut remaining = self.colors.clone();
+        let mut colors = Vec::with_capacity(remaining.len());
+
+        let darkest = remaining
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.relative_luminance().total_cmp(&b.relative_luminance()))
+            .map(|(index, _)| index);
+        let mut next = darkest;
+
+        while let Some(index) = next {
+            let current = remaining.swap_remove(index);
+            colors.push(current);
+
+            next = remaining
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| {
+                    current.difference(**a).total_cmp(&current.difference(**b))
+                })
+                .map(|(index, _)| index);
+        }
+
+        Palette { colors }
+    }
+
+    /// Returns the palette without colors that are near-duplicates of an earlier one, such as to clean up
+    /// a palette extracted from an image. The order of the colors that are kept is unchanged.
+    ///
+    /// Each color is kept only if its CIEDE2000 difference (see [`Rgb::difference`]) from every color
+    /// kept before it is at least `min_delta_e`, so the first color of each cluster is the one kept.
+    /// See [`crate::distance::JUST_NOTICEABLE_DIFFERENCE`] for a sensible threshold.
+    pub fn dedupe(&self, min_delta_e: f64) -> Palette {
+        let mut colors: Vec<Rgb> = Vec::with_capacity(self.colors.len());
+
+        for &color in &self.colors {
+            if colors
+                .iter()
+                .all(|kept| kept.difference(color) >= min_delta_e)
+            {
+                colors.push(color);
+            }
+        }
+
+        Palette { colors }
+    }
+
+    /// Returns the sum of the CIEDE2000 differences between each pair of neighboring colors.
+    /// A smaller total means a smoother palette. See [`Palette::sorted_perceptual`].
+    pub fn path_difference(&self) -> f64 {
+        self.colors
+            .windows(2)
+            .map(|pair| pair[0].difference(pair[1]))
+            .sum()
+    }
+}
+
+/// Returns the index into `palette` of the color nearest to each pixel, such as for the image data
+/// of a GIF or an indexed PNG. When several palette colors are equally near, the first is used.
+///
+/// The distance is the Euclidean distance in [`Oklab`], which is close to perceptually uniform,
+/// and the palette is put in a k-d tree so that each pixel takes logarithmic time to look up.
+/// If the palette is empty, there must also be no pixels.
+pub fn index_buffer(pixels: &[Rgb], palette: &[Rgb]) -> Result<Vec<u8>, IndexBufferError> {
+    if palette.len() > MAX_INDEXED_COLORS {
+        return Err(IndexBufferError::TooLarge(palette.len()));
+    } else if palette.is_empty() && !pixels.is_empty() {
+        return Err(IndexBufferError::Empty);
+    }
+
+    let mut tree = palette
+        .iter()
+        .zip(0..=u8::MAX)
+        .map(|(&color, index)| (Oklab::from(color).into(), index))
+        .collect::<Vec<_>>();
+    build_tree(&mut tree, 0);
+
+    Ok(pixels
+        .iter()
+        .map(|&pixel| {
+            let mut best = (f64::INFINITY, 0);
+            find_nearest(&tree, Oklab::from(pixel).into(), 0, &mut best);
+            best.1
+        })
+        .collect())
+}
+
+/// A point in a k-d tree with the index of its color in the palette.
+type TreeNode = ([f64; 3], u8);
+
+/// Arranges `nodes` into an implicit k-d tree, where the median along the axis for `depth`
+/// is in the middle of the slice, and the halves on either side are the subtrees.
+fn build_tree(nodes: &mut [TreeNode], depth: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % 3;
+    let middle = nodes.len() / 2;
+    nodes.select_nth_unstable_by(middle, |a, b| a.0[axis].total_cmp(&b.0[axis]));
+
+    let (lower, upper) = nodes.split_at_mut(middle);
+    build_tree(lower, depth + 1);
+    build_tree(&mut upper[1..], depth + 1);
+}
+
+/// Updates `best`, the squared distance and index of the nearest node so far, from the tree in `nodes`.
+fn find_nearest(nodes: &[TreeNode], target: [f64; 3], depth: usize, best: &mut (f64, u8)) {
+    if nodes.is_empty() {
+        return;
+    }
+
+    let middle = nodes.len() / 2;
+    let (point, index) = nodes[middle];
+    let distance = point
+        .iter()
+        .zip(target)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>();
+    if distance < best.0 || (distance == best.0 && index < best.1) {
+        *best = (distance, index);
+    }
+
+    let offset = target[depth % 3] - point[depth % 3];
+    let (near, far) = if offset < 0.0 {
+        (&nodes[..middle], &nodes[middle + 1..])
+    } else {
+        (&nodes[middle + 1..], &nodes[..middle])
+    };
+
+    find_nearest(near, target, depth + 1, best);
+    // The other side can only be nearer if the splitting plane is.
+    if offset * offset <= best.0 {
+        find_nearest(far, target, depth + 1, best);
+    }
+}
+
+impl From<Vec<Rgb>> for Palette {
+    fn from(colors: Vec<Rgb>) -> Self {
+        Self { colors }
+    }
+}
+
+impl FromIterator<Rgb> for Palette {
+    fn from_iter<I: IntoIterator<Item = Rgb>>(iter: I) -> Self {
+        Self {
+            colors: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    #[test]
+    fn test_sorted_perceptual() {
+        let gradient = crate::gradient(
+            Rgb::from_hex("#102B68").unwrap(),
+            Rgb::from_hex("#F3AF0B").unwrap(),
+            12,
+        );
+        // A fixed shuffle, so that the test is deterministic
+        let shuffled = [7, 2, 10, 0, 5, 11, 3, 8, 1, 6, 9, 4]
+            .into_iter()
+            .map(|index| gradient[index])
+            .collect::<Palette>();
+        let sorted = shuffled.sorted_perceptual();
+
+        assert!(sorted.path_difference() < shuffled.path_difference());
+        assert_eq!(sorted.colors.len(), shuffled.colors.len());
+        assert_eq!(sorted.colors[0], gradient[0]);
+    }
+
+    #[test]
+    fn test_sorted_perceptual_empty() {
+        assert_eq!(Palette::default().sorted_perceptual(), Palette::default());
+    }
+
+    #[test]
+    fn test_dedupe() {
+        let (a, b) = (Rgb::from([0.5, 0.5, 0.5]), Rgb::from([0.51, 0.51, 0.51]));
+        assert!(
+            (0.75..1.25).contains(&a.difference(b)),
+            "{}",
+            a.difference(b)
+        );
+
+        let palette = Palette::from(vec![a, Rgb::from_hex("#C0392B").unwrap(), b]);
+
+        assert_eq!(
+            palette.dedupe(2.0).colors,
+            [palette.colors[0], palette.colors[1]]
+        );
+        assert_eq!(palette.dedupe(0.5), palette);
+        assert_eq!(Palette::default().dedupe(2.0), Palette::default());
+    }
+
+    #[test]
+    fn test_index_buffer_exact() {
+        let palette = crate::gradient(
+            Rgb::from_hex("#102B68").unwrap(),
+            Rgb::from_hex("#F3AF0B").unwrap(),
+            40,
+        );
+        let pixels = [39, 0, 17, 17, 5, 22].map(|index| palette[index]);
+
+        assert_eq!(
+            index_buffer(&pixels, &palette).unwrap(),
+            [39, 0, 17, 17, 5, 22]
+        );
+    }
+
+    #[test]
+    fn test_index_buffer_nearest() {
+        let palette = [0x000000, 0xFF0000, 0x00FF00, 0x0000FF, 0xFFFFFF].map(Rgb::from);
+        // Every pixel, compared against a brute-force search
+        let pixels = (0..=u8::MAX)
+            .step_by(15)
+            .flat_map(|r| [Rgb::from([r, 255 - r, r / 2]), Rgb::from([r / 3, r, 200])])
+            .collect::<Vec<_>>();
+
+        let brute_force = pixels.iter().map(|&pixel| {
+            let distance = |color: Rgb| {
+                let (a, b) = (Oklab::from(pixel), Oklab::from(color));
+                (a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)
+            };
+            (0..palette.len())
+                .min_by(|&a, &b| distance(palette[a]).total_cmp(&distance(palette[b])))
+                .unwrap() as u8
+        });
+
+        assert!(index_buffer(&pixels, &palette)
+            .unwrap()
+            .into_iter()
+            .eq(brute_force));
+    }
+
+    #[test]
+    fn test_index_buffer_too_large() {
+        let palette = vec![Rgb::from([0.5, 0.5, 0.5]); 257];
+
+        assert_eq!(
+            index_buffer(&[], &palette),
+            Err(IndexBufferError::TooLarge(257))
+        );
+        assert_eq!(
+            IndexBufferError::TooLarge(257).to_string(),
+            "the palette had 257 colors, but indexed color allows at most 256"
+        );
+    }
+
+    #[test]
+    fn test_index_buffer_empty() {
+        let pixels = [Rgb::from([0.5, 0.5, 0.5])];
+
+        assert_eq!(index_buffer(&pixels, &[]), Err(IndexBufferError::Empty));
+        assert_eq!(index_buffer(&[], &[]), Ok(Vec::new()));
+    }
+}
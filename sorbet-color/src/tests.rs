@@ -0,0 +1,600 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use once_cell::sync::Lazy;
+use test_case::test_case;
+
+#[cfg(feature = "image")]
+#[test]
+fn test_to_swatch_png() {
+    let bytes = Rgb::from([0.2, 0.4, 0.6]).to_swatch_png(32, 16);
+    let image = image::load_from_memory(&bytes).unwrap().to_rgb8();
+
+    assert_eq!(image.dimensions(), (32, 16));
+    assert_eq!(image.get_pixel(16, 8).0, [51, 102, 153]);
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_to_swatch_png_translucent() {
+    let bytes = Rgba::from([0.0, 0.0, 0.0, 0.5]).to_swatch_png(16, 16);
+    let image = image::load_from_memory(&bytes).unwrap().to_rgb8();
+
+    // Half-transparent black over the white and gray squares of the checkerboard
+    assert_eq!(image.get_pixel(0, 0).0, [128, 128, 128]);
+    assert_eq!(image.get_pixel(8, 0).0, [102, 102, 102]);
+}
+
+#[test]
+fn test_with_alpha_from() {
+    let source = Rgba::from([0.0, 0.0, 0.0, 0.3]);
+    let color = Hsla::from(Rgb::from([0.2, 0.4, 0.6])).with_alpha_from(&source);
+
+    assert!(
+        (color.alpha - 0.3).abs() < 1e-12,
+        "alpha was {}",
+        color.alpha
+    );
+    assert_eq!(color.rgb_array(), [51, 102, 153]);
+
+    let opaque = Rgb::from([0.2, 0.4, 0.6]);
+
+    assert_eq!(opaque.with_alpha_from(&source), opaque);
+}
+
+#[test]
+fn test_within_delta_e() {
+    let color = Hsl::from([210.0, 0.5, 0.4]);
+
+    assert!(color.within_delta_e(&color, 1.0));
+    assert!(!color.within_delta_e(
+        &Hsl::from([30.0, 0.5, 0.4]),
+        distance::JUST_NOTICEABLE_DIFFERENCE
+    ));
+}
+
+#[test_case("red" ; "named")]
+#[test_case("#f00" ; "shorthand hex")]
+#[test_case("rgb(255,0,0)" ; "functional")]
+#[test_case("ff0000" ; "bare hex")]
+#[test_case("0xFF0000FF" ; "prefixed hex with alpha")]
+#[test_case(" oklch(62.8% 0.2577 29.23) " ; "modern functional")]
+fn test_parse_any(string: &str) {
+    let color = parse_any(string).unwrap();
+
+    assert_eq!(Rgba::from(color).rgba_array(), [255, 0, 0, 255]);
+}
+
+#[test]
+fn test_parse_any_unrecognized() {
+    let error = parse_any("not a color").unwrap_err();
+
+    assert!(matches!(
+        error,
+        css::Error::UnrecognizedColor { ref input, .. } if input == "not a color"
+    ));
+}
+
+#[test]
+fn test_contrast_report() {
+    let report = Hsl::from([0.0, 0.0, 0.0]).contrast_report(Rgb::from([1.0, 1.0, 1.0]));
+
+    assert!((report.ratio - 21.0).abs() < 1e-12);
+    assert!(report.aaa_normal);
+}
+
+#[test]
+fn test_lerp() {
+    let black = Hsl::from(Rgb::from([0.0, 0.0, 0.0]));
+    let white = Hsl::from(Rgb::from([1.0, 1.0, 1.0]));
+
+    assert_eq!(lerp(black, white, 0.5).rgb_array(), [128, 128, 128]);
+    assert_eq!(lerp(black, white, -1.0), black);
+    assert_eq!(lerp(black, white, 2.0), white);
+    assert_eq!(
+        lerp(
+            Rgba::from([1.0, 0.0, 0.0, 0.0]),
+            Rgba::from([0.0, 0.0, 1.0, 1.0]),
+            0.25
+        ),
+        Rgba::from([0.75, 0.0, 0.25, 0.25])
+    );
+}
+
+#[test]
+fn test_gradient() {
+    let black = Hsl::from([0.0, 0.0, 0.0]);
+    let white = Hsl::from([0.0, 0.0, 1.0]);
+    let ramp = gradient(black, white, 5);
+
+    assert_eq!(ramp.len(), 5);
+    assert_eq!(ramp[0], black);
+    assert_eq!(ramp[4], white);
+    assert_eq!(ramp[2].rgb_array(), [128, 128, 128]);
+
+    assert_eq!(gradient(black, white, 1), vec![black]);
+    assert!(gradient(black, white, 0).is_empty());
+}
+
+#[test]
+fn test_gradient_oklch() {
+    let red = Rgba::from([1.0, 0.0, 0.0, 1.0]);
+    let blue = Rgba::from([0.0, 0.0, 1.0, 0.0]);
+    let ramp = gradient_oklch(red, blue, 3);
+    let middle = Oklch::from(ramp[1]);
+
+    assert_eq!((ramp[0], ramp[2]), (red, blue));
+    assert!((ramp[1].alpha - 0.5).abs() < 1e-12);
+    // The hue of the midpoint lies between red and blue, through magenta
+    assert!((300.0..340.0).contains(&middle.h), "hue was {}", middle.h);
+}
+
+#[test]
+fn test_to_color_p3_css() {
+    let string = Rgb::from([1.0, 0.0, 0.0]).to_color_p3_css();
+
+    assert_eq!(string, "color(display-p3 0.917 0.2 0.139 / 1)");
+
+    // Read the coordinates back and convert them to sRGB
+    let channels = string
+        .trim_start_matches("color(display-p3 ")
+        .split(' ')
+        .take(3)
+        .map(|channel| channel.parse().unwrap())
+        .collect::<Vec<f64>>();
+    let rgb = Rgb::from(DisplayP3::from([channels[0], channels[1], channels[2]]));
+
+    assert_eq!(<[u8; 3]>::from(rgb), [255, 0, 0]);
+}
+
+#[test]
+fn test_sample_stops() {
+    let stops = [
+        (0.0, Rgba::from([0.0, 0.0, 0.0, 1.0])),
+        (0.5, Rgba::from(Oklab::from([0.5, 0.0, 0.0]))),
+        (1.0, Rgba::from([1.0, 1.0, 1.0, 0.0])),
+    ];
+
+    // Halfway between the second and third stops
+    let sampled = Rgba::sample_stops(&stops, 0.75);
+    let Oklab { l, a, b } = sampled.into();
+
+    assert!((l - 0.75).abs() < 1e-6, "l was {l}");
+    assert!(a.abs() < 1e-6 && b.abs() < 1e-6, "a was {a}, b was {b}");
+    assert!((sampled.alpha - 0.5).abs() < 1e-12);
+
+    // Clamped outside of the stops
+    assert_eq!(Rgba::sample_stops(&stops, -1.0), stops[0].1);
+    assert_eq!(Rgba::sample_stops(&stops, 2.0), stops[2].1);
+}
+
+#[test]
+fn test_sample_stops_in() {
+    let stops = [
+        (0.0, Rgb::from([1.0, 0.0, 0.0])),
+        (1.0, Rgb::from([0.0, 0.0, 1.0])),
+    ];
+    let sampled = |space| Rgb::sample_stops_in(&stops, 0.5, space);
+
+    assert_eq!(
+        sampled(BlendSpace::default()),
+        Rgb::sample_stops(&stops, 0.5)
+    );
+    assert_eq!(sampled(BlendSpace::Srgb), Rgb::from([0.5, 0.0, 0.5]));
+    // Interpolating in linear light gives a brighter purple than the gamma-encoded channels.
+    assert!(sampled(BlendSpace::LinearRgb).r > sampled(BlendSpace::Srgb).r);
+    assert_ne!(
+        sampled(BlendSpace::Lab).hex(),
+        sampled(BlendSpace::Oklab).hex()
+    );
+    assert_eq!(
+        Rgb::sample_stops_in(&stops, 1.0, BlendSpace::Lab),
+        stops[1].1
+    );
+}
+
+#[test]
+fn test_rgba_bytes_round_trip() {
+    let color = Hsl::from([210.0, 0.5, 0.4]);
+    let bytes = color.rgba_bytes();
+
+    assert_eq!(bytes, [51, 102, 153, 255]);
+    assert_eq!(Hsl::from_rgba_bytes(bytes).rgba_bytes(), bytes);
+}
+
+color_newtype! {
+    /// An accent color used by the tests.
+    pub Accent(Rgba)
+}
+
+#[test]
+fn test_color_newtype() {
+    let accent = Accent::new("#FF8000").unwrap();
+
+    assert_eq!(accent.hex(), "#FF8000FF");
+    assert_eq!(accent.to_string(), Rgba::from(accent).to_string());
+    assert_eq!(Hsl::from(accent), Hsl::from(accent.0));
+    assert_eq!(Accent::from(Rgb::from(accent)), accent);
+}
+
+#[test]
+fn test_luminance_key_monotonic() {
+    let keys = (0..=255)
+        .map(|value| Rgb::from([value; 3]).luminance_key())
+        .collect::<Vec<_>>();
+
+    assert_eq!(keys[0], 0);
+    assert_eq!(keys[255], u32::MAX);
+    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn test_to_linear_rgba_f32() {
+    let [r, g, b, alpha] = Rgba::from([0.5, 0.5, 0.5, 0.25]).to_linear_rgba_f32();
+
+    for channel in [r, g, b] {
+        assert!((channel - 0.214).abs() < 1e-3, "channel was {channel}");
+    }
+    assert_eq!(alpha, 0.25);
+}
+
+#[test]
+fn test_from_linear_rgba_f32_round_trip() {
+    let color = Rgba::from([51, 102, 153, 128]);
+
+    assert_eq!(
+        Rgba::from_linear_rgba_f32(color.to_linear_rgba_f32()).rgba_array(),
+        color.rgba_array()
+    );
+}
+
+#[test]
+fn test_parse_or() {
+    let fallback = Rgb::from([0.0, 0.0, 1.0]);
+
+    assert_eq!(Rgb::parse_or("rgb(nope)", fallback), fallback);
+    assert_eq!(
+        Rgb::parse_or("#FF0000", fallback),
+        Rgb::from([1.0, 0.0, 0.0])
+    );
+    assert_eq!(Rgb::parse_or_else("#GG0000", || fallback), fallback);
+    assert_eq!(
+        Rgb::parse_or_else("rgb(255, 0, 0)", || unreachable!()),
+        Rgb::from([1.0, 0.0, 0.0])
+    );
+}
+
+#[test]
+fn test_new_leading_dot_and_exponent() {
+    let color = Rgba::new("rgba(.5, 1e2, 0, .3)").unwrap();
+
+    assert_eq!(color.r, 0.5 / 255.0);
+    assert_eq!(color.g, 100.0 / 255.0);
+    assert_eq!(color.b, 0.0);
+    assert_eq!(color.alpha, 0.3);
+}
+
+#[test]
+fn test_new_malformed_exponent() {
+    assert!(matches!(
+        Rgb::new("rgb(1e, 0, 0)"),
+        Err(css::Error::InvalidCssFloat { index: 4, .. })
+    ));
+}
+
+#[test_case("#ff0000" => "red" ; "red")]
+#[test_case("#6495ED" => "cornflowerblue" ; "cornflowerblue")]
+#[test_case("hsl(0, 0%, 100%)" => "white" ; "white")]
+fn test_nearest_named_exact(string: &str) -> &'static str {
+    let (name, difference) = Rgb::new(string).unwrap().nearest_named();
+    assert!(difference < 1e-9, "{string} is {difference} from {name}");
+
+    name
+}
+
+#[test]
+fn test_nearest_named_distance() {
+    let (name, difference) = Rgba::from([0.98, 0.02, 0.01, 1.0]).nearest_named();
+
+    assert_eq!(name, "red");
+    assert!(difference > 0.1 && difference < 5.0, "{difference}");
+}
+
+static TEST_COLORS: Lazy<Vec<&str>> = Lazy::new(|| {
+    "#353B48, #666666, #444852, #FCFCFC, #434343, #90939B, #353537, #2B303B, #B6B8C0, #241F31, #303440, #000000, #9398A2, #DFDFDF, #F0F1F2, #CFCFCF, #D3D8E2, #505666, #808080, #8A939F, #282B36, #AFB8C6, #383838, #4DADD4, #353A48, #838383, #202229, #7A7F8A, #7A7F8B, #2E3340, #70788D, #66A1DC, #17191F, #D7D7D7, #545860, #39404D, #161A26, #BE3841, #3C4049, #2F3A42, #F0F2F5, #4E4EFF, #262934, #1D1F26, #404552, #353945, #383C45, #8F939D, #F7EF45, #A4AAB7, #B2CDF1, #444A58, #BAC3CF, #FF00FF, #F46067, #5C6070, #C7CACF, #525762, #FF0B00, #323644, #F75A61, #464646, #ECEDF0, #171717, #E01B24, #1B1B1B, #797D87, #15171C, #8C919D, #4D4F52, #5B627B, #728495, #454C5C, #4080FB, #E2E2E2, #D1D3DA, #C0E3FF, #3580E4, #B7C0D3, #232428, #2D323F, #6E6E6E, #DCDCDC, #B9BCC2, #CC575D, #A1A1A1, #52555E, #353A47, #7C818C, #979DAC, #2F343F, #DDE3E9, #828282, #C5DCF7, #001AFF, #722563, #AFB8C5, #222529, #8ABFDD, #666A74, #F68086, #EDF5FB, #4B5162, #A9ACB2, #786613, #C7C7C7, #EEEFF1, #2B2E37, #F70505, #292C36, #3E434F, #5C616C, #F57900, #2D303B, #F5F6F7, #5F697F, #2E3436, #808791, #F08437, #CBD2E3, #E5A50A, #EEEEEE, #252932, #E7E8EB, #3E4350, #FF1111, #EF2929, #FC4138, #FCFDFD, #7A7A7A, #21242B, #BEBEBE, #FFFFFF, #252A35, #5252FF, #767B87, #535353, #3E3E3E, #AA5555, #5F6578, #C4C7CC, #383C4A, #102B68, #21252B, #F3AF0B, #CFD6E6, #D7787D, #FF7A80, #FDFDFD, #398DD3, #A51D2D, #73D216, #F8F8F9, #262932, #2F343B, #2B2E39, #2D3036, #F04A50, #006098, #3F4453, #AD4242, #1B1C21, #B9BFCE, #FF1616, #E5E5E5, #ED686F, #EAEBED, #FBFCFC, #398CD3, #262933, #5294E2, #0000FF, #D7D8DD, #2B2F3B, #F13039, #999999, #1F1F1F, #50DBB5, #525252, #FF2121, #F27835, #91949C, #ADAFB5, #3B3C3E, #D3D4D8, #525D76, #434652, #CACACA, #2D323D, #F9FAFB, #617C95, #EDEDED, #1A1A1A, #D8354A, #90949E, #313541, #A8A8A8, #DBDFE3, #CECECE, #0F0F0F, #1D242A, #B8BABF, #0F1116, #EEF4FC, #E2E7EF, #D3DAE3".split(", ").collect()
+});
+
+/// Converts every color in [`TEST_COLORS`] from `$a` to `$b` and back,
+/// asserting that the 8-bit channels survive the round-trip.
+macro_rules! assert_round_trip {
+    ($a:ident, $b:ident) => {
+        for hex in TEST_COLORS.iter() {
+            let color = $a::from(Rgba::from_hex(hex).unwrap());
+            let round_trip = $a::from($b::from(color));
+
+            assert_eq!(
+                round_trip.rgba_array(),
+                color.rgba_array(),
+                "{} -> {} -> {} failed for {hex}",
+                stringify!($a),
+                stringify!($b),
+                stringify!($a),
+            );
+        }
+    };
+}
+
+/// Calls [`assert_round_trip`] for every ordered pair of the given types.
+macro_rules! assert_round_trip_matrix {
+    ( $( $a:ident ),+ ) => {
+        assert_round_trip_matrix!(@outer [ $( $a ),+ ] $( $a ),+);
+    };
+    (@outer $all:tt $( $a:ident ),+ ) => {
+        $( assert_round_trip_matrix!(@inner $a $all); )+
+    };
+    (@inner $a:ident [ $( $b:ident ),+ ] ) => {
+        $( assert_round_trip!($a, $b); )+
+    };
+}
+
+#[test]
+fn test_round_trip_matrix() {
+    assert_round_trip_matrix!(Rgb, Rgba, Hsv, Hsva, Hsl, Hsla, Xyz, Oklab, Oklch, Cmyk);
+}
+
+#[test]
+fn test_hue_seam() {
+    assert_eq!(Hsv::from([360.0, 1.0, 1.0]).rgb_array(), [255, 0, 0]);
+    assert_eq!(Hsl::from([360.0, 1.0, 0.5]).rgb_array(), [255, 0, 0]);
+    assert_eq!(Hsl::from([-120.0, 1.0, 0.5]).rgb_array(), [0, 0, 255]);
+}
+
+#[test]
+fn test_array_round_trip() {
+    let hsla = Hsla::from(Rgba::from_hex("#4DADD480").unwrap());
+    let hsva = Hsva::from(hsla);
+
+    assert_eq!(Hsla::from(<[f64; 4]>::from(hsla)), hsla);
+    assert_eq!(Hsva::from(<[f64; 4]>::from(hsva)), hsva);
+}
+
+#[test_case(Hsl::from([120.0, 0.5, 0.5]).to_string() => "hsl(120, 50%, 50%)" ; "hsl")]
+#[test_case(Hsv::from([120.0, 0.5, 0.5]).to_string() => "hsv(120, 50%, 50%)" ; "hsv")]
+#[test_case(Hsla::from([120.0, 0.5, 0.5, 0.25]).to_string() => "hsla(120, 50%, 50%, 25%)" ; "hsla")]
+#[test_case(Hsva::from([120.0, 0.5, 0.5, 0.25]).to_string() => "hsva(120, 50%, 50%, 25%)" ; "hsva")]
+fn test_display_hue_types(string: String) -> String {
+    string
+}
+
+#[test]
+fn test_mix_iso_luminance() {
+    let source = Rgb::from_hex("#3580E4").unwrap();
+    let other = Rgb::from_hex("#E01B24").unwrap();
+    let lightness = Oklab::from(source).l;
+
+    for step in 0..=10 {
+        let mixed = source.mix_iso_luminance(other, step as f64 / 10.0);
+
+        assert!(
+            (Oklab::from(mixed).l - lightness).abs() < 1e-6,
+            "step {}",
+            step
+        );
+    }
+
+    let end = Oklch::from(Oklab::from(source.mix_iso_luminance(other, 1.0)));
+
+    assert!((end.h - Oklch::from(Oklab::from(other)).h).abs() < 1e-3);
+}
+
+#[test]
+fn test_to_hsl_display() {
+    let color = Hsl::from([210.0, 0.5, 0.4]);
+
+    assert_eq!(color.to_hsl_display(), "hsl(210°, 50%, 40%)");
+    assert_eq!(Rgb::from(color).to_hsl_display(), "hsl(210°, 50%, 40%)");
+    assert_eq!(color.to_hsv_display(), "hsv(210°, 67%, 60%)");
+    assert_eq!(
+        Hsl::from([359.7, 1.0, 0.5]).to_hsl_display(),
+        "hsl(0°, 100%, 50%)"
+    );
+}
+
+#[test]
+fn test_mix() {
+    let black = Hsl::from([0.0, 0.0, 0.0]);
+    let white = Hsl::from([0.0, 0.0, 1.0]);
+
+    assert_eq!(black.mix(white, 0.5).rgb_array(), [128, 128, 128]);
+    assert_eq!(black.mix(white, 2.0), white);
+    assert_eq!(black.mix(white, 0.25), lerp(black, white, 0.25));
+}
+
+#[test]
+fn test_with_channels_round_trip() {
+    let hsla = Hsla::from([210.0, 0.5, 0.4, 0.8]);
+    let cmyk = Cmyk::from(Rgb::from_hex("#4DADD4").unwrap());
+
+    assert_eq!(hsla.with_channels(&hsla.component_values()), Some(hsla));
+    assert_eq!(cmyk.with_channels(&cmyk.component_values()), Some(cmyk));
+    assert_eq!(
+        hsla.with_channels(&[-90.0, 2.0, 0.5, 1.0]),
+        Some(Hsla::from([270.0, 1.0, 0.5, 1.0]))
+    );
+    assert_eq!(hsla.with_channels(&[210.0, 0.5, 0.4]), None);
+}
+
+#[test]
+fn test_ensure_distinct() {
+    let existing = [
+        Rgb::from_hex("#E01B24").unwrap(),
+        Rgb::from_hex("#3580E4").unwrap(),
+    ];
+    let near_duplicate = Rgb::from_hex("#E3202A").unwrap();
+    let distinct = near_duplicate.ensure_distinct(&existing, 20.0);
+
+    assert!(existing
+        .iter()
+        .all(|other| distinct.difference(*other) >= 20.0));
+
+    // A color that is already distinct is returned as it is
+    let green = Rgb::from_hex("#73D216").unwrap();
+
+    assert_eq!(
+        green.ensure_distinct(&existing, 20.0).rgb_array(),
+        green.rgb_array()
+    );
+}
+
+#[test_case("rgb(300, 0, 0)" => false ; "rgb float")]
+#[test_case("rgb(100%, 0%, 120%)" => false ; "rgb percent")]
+#[test_case("hsl(400, 50%, 50%)" => false ; "hue")]
+#[test_case("hsl(-30, 50%, 50%)" => false ; "negative hue")]
+#[test_case("hsl(360deg, 50%, 50%)" => true ; "full turn")]
+#[test_case("hsv(1.5turn, 50%, 50%)" => false ; "hue in turns")]
+#[test_case("oklch(70% 0.1 400)" => false ; "oklch hue")]
+#[test_case("hsla(120, 150%, 50%, 0.5)" => false ; "saturation")]
+#[test_case("oklch(70% 0.1 240 / 2)" => false ; "alpha")]
+#[test_case("lab(50% 200 -200)" => true ; "unbounded lab")]
+#[test_case("lab(150% 0 0)" => false ; "lab lightness percent")]
+#[test_case("lab(150 0 0)" => false ; "lab lightness")]
+#[test_case("oklch(-10% 0.1 240)" => false ; "oklch negative lightness")]
+#[test_case("oklab(1.5 0 0)" => false ; "oklab lightness")]
+#[test_case("lch(100% 50 120)" => true ; "lch full lightness")]
+#[test_case("rgb(255, 0, 0)" => true ; "in range")]
+fn test_new_strict(string: &str) -> bool {
+    assert!(Rgba::new(string).is_ok());
+
+    match Rgba::new_strict(string) {
+        Ok(color) => {
+            assert_eq!(color, Rgba::new(string).unwrap());
+            true
+        }
+        Err(css::Error::ValueOutOfRange) => false,
+        Err(error) => panic!("{string} failed to parse: {error}"),
+    }
+}
+
+#[test]
+fn test_withers() {
+    let base = Hsl::from([350.0, 0.5, 0.5]);
+
+    assert_eq!(base.with_hue(base.h + 30.0), Hsl::from([20.0, 0.5, 0.5]));
+    assert_eq!(base.with_saturation(0.25).s, 0.25);
+    assert_eq!(base.with_lightness(0.75).l, 0.75);
+    assert_eq!(base.with_alpha(0.5), Hsla::from([350.0, 0.5, 0.5, 0.5]));
+    assert_eq!(
+        Hsva::from([120.0, 0.5, 0.5, 1.0]).with_value(0.25),
+        Hsva::from([120.0, 0.5, 0.25, 1.0])
+    );
+    assert_eq!(
+        Rgb::from([1.0, 0.5, 0.0]).with_alpha(0.5),
+        Rgba::from([1.0, 0.5, 0.0, 0.5])
+    );
+    assert_eq!(Rgba::from([1.0, 0.5, 0.0, 1.0]).with_alpha(0.5).alpha, 0.5);
+}
+
+#[test_case(css::CssSyntax::Legacy, 0.5 => "hsla(210, 50%, 40%, 0.5)" ; "legacy translucent")]
+#[test_case(css::CssSyntax::Legacy, 1.0 => "hsl(210, 50%, 40%)" ; "legacy opaque")]
+#[test_case(css::CssSyntax::Modern, 0.5 => "hsl(210 50% 40% / 0.5)" ; "modern translucent")]
+#[test_case(css::CssSyntax::Modern, 1.0 => "hsl(210 50% 40%)" ; "modern opaque")]
+fn test_to_css_hsl(syntax: css::CssSyntax, alpha: f64) -> String {
+    let color = Hsla::from([210.0, 0.5, 0.4, alpha]);
+    let css = color.to_css_hsl(syntax);
+
+    assert_eq!(Hsla::new(&css).unwrap(), color);
+    css
+}
+
+#[test]
+fn test_parse_all() {
+    let colors = Rgb::parse_all(TEST_COLORS.join(", ")).unwrap();
+
+    assert_eq!(colors.len(), TEST_COLORS.len());
+    assert_eq!(colors[2], Rgb::from_hex("#444852").unwrap());
+
+    let mixed = Rgba::parse_all("#ff0000,\nrgba(0, 0, 255, 50%)\n\nhsl(120, 100%, 25%),").unwrap();
+    assert_eq!(mixed.len(), 3);
+    assert_eq!(mixed[1], Rgba::from([0.0, 0.0, 1.0, 0.5]));
+
+    let error = Rgb::parse_all("#000000, #ffffff, rgb(nope), #ff0000").unwrap_err();
+    assert!(
+        matches!(error, css::Error::InvalidListItem { index: 2, .. }),
+        "{error:?}"
+    );
+}
+
+#[test]
+fn test_new_error_position() {
+    let string = "  rgb(10, 20, 3o)";
+    let error = Rgb::new(string).unwrap_err();
+
+    assert_eq!(error.index(), Some(14));
+    assert_eq!(&string[14..], "3o)");
+}
+
+#[test]
+fn test_min_contrast_against() {
+    let text = Hsl::from([0.0, 0.0, 1.0]);
+    let backgrounds = gradient(
+        Rgb::from_hex("#102B68").unwrap(),
+        Rgb::from_hex("#F3AF0B").unwrap(),
+        8,
+    );
+    let ratios = backgrounds
+        .iter()
+        .map(|&background| luminance::contrast_ratio(text.into(), background));
+
+    assert_eq!(
+        text.min_contrast_against(&backgrounds),
+        ratios.fold(f64::INFINITY, f64::min)
+    );
+    assert_eq!(
+        text.min_contrast_against(&backgrounds),
+        luminance::contrast_ratio(text.into(), backgrounds[7])
+    );
+    assert_eq!(text.min_contrast_against(&[]), f64::INFINITY);
+}
+
+#[test]
+fn test_blend_even() {
+    let hues = [0.0, 120.0, 240.0].map(|hue| Hsl::from([hue, 1.0, 0.5]));
+    let blended = Oklch::from(Oklab::from(Hsl::blend_even(&hues).unwrap()));
+
+    assert!(blended.c < 0.02, "{blended:?} is not close to gray");
+    assert_eq!(Hsl::blend_even(&hues[1..2]), Some(hues[1]));
+    assert_eq!(Hsl::blend_even(&[]), None);
+}
+
+#[test]
+fn test_blend_even_in() {
+    let (red, blue) = (Rgb::from([1.0, 0.0, 0.0]), Rgb::from([0.0, 0.0, 1.0]));
+    let blended = |space| Rgb::blend_even_in(&[red, blue], space).unwrap();
+
+    assert_eq!(
+        blended(BlendSpace::default()),
+        Rgb::blend_even(&[red, blue]).unwrap()
+    );
+    assert_eq!(blended(BlendSpace::Srgb), red.mix(blue, 0.5));
+    // Averaging in linear light gives a brighter purple than the gamma-encoded channels.
+    assert!(blended(BlendSpace::LinearRgb).r > blended(BlendSpace::Srgb).r);
+    assert_ne!(
+        blended(BlendSpace::Lab).hex(),
+        blended(BlendSpace::Oklab).hex()
+    );
+    assert_eq!(Rgb::blend_even_in(&[red], BlendSpace::Lab), Some(red));
+}
@@ -0,0 +1,176 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{css, types::*};
+
+/// A color of any of the types in this crate that can be parsed from a string,
+/// for when the type is only known at runtime. See [`crate::parse_any`].
+///
+/// To work with the color, convert it to [`Rgb`] or [`Rgba`] with [`From`],
+/// or match on the variants to get the color in the space it was written in.
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AnyColor {
+    Rgb(Rgb),
+    Rgba(Rgba),
+    Hsv(Hsv),
+    Hsva(Hsva),
+    Hsl(Hsl),
+    Hsla(Hsla),
+    Lab(Lab),
+    Lch(Lch),
+    Oklab(Oklab),
+    Oklch(Oklch),
+}
+
+/// The color spaces of the variants of [`AnyColor`], such as for storing which space a color
+/// was written in alongside its channels. This can be parsed from and displayed as the lowercase
+/// name of the type, such as `hsl`. See [`AnyColor::from_components_in`].
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, strum::EnumString, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum ColorSpace {
+    Rgb,
+    Rgba,
+    Hsv,
+    Hsva,
+    Hsl,
+    Hsla,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+}
+
+macro_rules! impl_any_color {
+    ( $( $t:ident: $n:literal ),+ ) => {
+        $(
+            impl From<$t> for AnyColor {
+                fn from(other: $t) -> Self {
+                    Self::$t(other)
+                }
+            }
+        )+
+
+        impl AnyColor {
+            /// Returns the color space of the variant.
+            pub fn space(&self) -> ColorSpace {
+                match self {
+                    $( Self::$t(_) => ColorSpace::$t, )+
+                }
+            }
+
+            /// Creates the color of the type for `space` from its channels, in the order of
+            /// the fields of that type and in the same ranges, such as `[210.0, 0.5, 0.4]` for
+            /// [`ColorSpace::Hsl`]. This is the inverse of [`AnyColor::space`] and the array
+            /// conversion of the color, for reading records of a space and its channels.
+            ///
+            /// Returns [`None`] if the length of `values` is not the number of channels of the space.
+            /// The channels are not checked or clamped.
+            pub fn from_components_in(space: ColorSpace, values: &[f64]) -> Option<Self> {
+                match space {
+                    $( ColorSpace::$t => Some(Self::$t(<[f64; $n]>::try_from(values).ok()?.into())), )+
+                }
+            }
+        }
+
+        impl From<AnyColor> for Rgb {
+            fn from(other: AnyColor) -> Self {
+                match other {
+                    $( AnyColor::$t(color) => color.into(), )+
+                }
+            }
+        }
+
+        impl From<AnyColor> for Rgba {
+            fn from(other: AnyColor) -> Self {
+                match other {
+                    $( AnyColor::$t(color) => color.into(), )+
+                }
+            }
+        }
+    };
+}
+
+impl_any_color!(Rgb: 3, Rgba: 4, Hsv: 3, Hsva: 4, Hsl: 3, Hsla: 4, Lab: 3, Lch: 3, Oklab: 3, Oklch: 3);
+
+//
+// Implement to/from CssColorNotation
+//
+
+impl TryFrom<&css::CssColorNotation> for AnyColor {
+    type Error = css::Error;
+
+    /// Converts the notation to the color type that matches its format.
+    /// The CSS Color 4 formats have no counterpart types with an alpha channel,
+    /// so if they have an alpha other than `1.0` the color is converted to [`Rgba`] instead.
+    fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
+        let alpha = css::css_optional_alpha(other);
+        let with_alpha = |color: AnyColor| {
+            if alpha == 1.0 {
+                color
+            } else {
+                Self::Rgba(Rgba {
+                    alpha,
+                    ..color.into()
+                })
+            }
+        };
+
+        Ok(match other.format {
+            // The formats without an `a` suffix may still have an alpha channel.
+            css::CssColorType::Rgb if other.values.len() == 3 => Self::Rgb(other.try_into()?),
+            css::CssColorType::Hsv if other.values.len() == 3 => Self::Hsv(other.try_into()?),
+            css::CssColorType::Hsl if other.values.len() == 3 => Self::Hsl(other.try_into()?),
+            css::CssColorType::Rgb | css::CssColorType::Rgba => Self::Rgba(other.try_into()?),
+            css::CssColorType::Hsv | css::CssColorType::Hsva => Self::Hsva(other.try_into()?),
+            css::CssColorType::Hsl | css::CssColorType::Hsla => Self::Hsla(other.try_into()?),
+            css::CssColorType::Lab => with_alpha(Self::Lab(other.try_into()?)),
+            css::CssColorType::Lch => with_alpha(Self::Lch(other.try_into()?)),
+            css::CssColorType::Oklab => with_alpha(Self::Oklab(other.try_into()?)),
+            css::CssColorType::Oklch => with_alpha(Self::Oklch(other.try_into()?)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_notation() {
+        let parse = |string: &str| {
+            AnyColor::try_from(&string.parse::<css::CssColorNotation>().unwrap()).unwrap()
+        };
+
+        assert!(matches!(parse("hsl(210, 50%, 40%)"), AnyColor::Hsl(_)));
+        assert!(matches!(parse("oklch(70% 0.1 240)"), AnyColor::Oklch(_)));
+        assert_eq!(Rgba::from(parse("oklch(70% 0.1 240 / 50%)")).alpha, 0.5);
+    }
+
+    #[test]
+    fn test_from_components_in() {
+        let color = AnyColor::from_components_in(ColorSpace::Hsl, &[210.0, 0.5, 0.4]);
+
+        assert_eq!(color, Some(AnyColor::Hsl(Hsl::from([210.0, 0.5, 0.4]))));
+        assert_eq!(color.unwrap().space(), ColorSpace::Hsl);
+        assert_eq!(
+            AnyColor::from_components_in(ColorSpace::Hsla, &[210.0, 0.5, 0.4]),
+            None
+        );
+        assert_eq!("oklch".parse(), Ok(ColorSpace::Oklch));
+    }
+}
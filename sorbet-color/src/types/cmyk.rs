@@ -0,0 +1,166 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{types::*, Color};
+
+/// This structure represents colors in the subtractive CMYK color model with
+/// cyan, magenta, yellow, and key (black) ink channels.
+/// See the [Wikipedia reference](<https://en.wikipedia.org/wiki/CMYK_color_model>) for details.
+///
+/// Conversions to and from [`Rgb`] use the naive formula, where the black channel
+/// is generated from the brightest RGB channel. No ink or paper profile is taken into account.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cmyk {
+    /// Cyan ink channel.
+    /// Ranged `0.0..1.0`.
+    pub c: f64,
+    /// Magenta ink channel.
+    /// Ranged `0.0..1.0`.
+    pub m: f64,
+    /// Yellow ink channel.
+    /// Ranged `0.0..1.0`.
+    pub y: f64,
+    /// Key (black) ink channel.
+    /// Ranged `0.0..1.0`.
+    pub k: f64,
+}
+
+impl Color for Cmyk {
+    fn hex(&self) -> String {
+        Rgb::from(*self).hex()
+    }
+}
+
+impl Eq for Cmyk {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for Cmyk {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.c.to_bits().hash(state);
+        self.m.to_bits().hash(state);
+        self.y.to_bits().hash(state);
+        self.k.to_bits().hash(state);
+    }
+}
+
+impl Cmyk {
+    /// Returns the color with every channel clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        Self {
+            c: self.c.clamp(0.0, 1.0),
+            m: self.m.clamp(0.0, 1.0),
+            y: self.y.clamp(0.0, 1.0),
+            k: self.k.clamp(0.0, 1.0),
+        }
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 4]> for Cmyk {
+    fn from(array: [f64; 4]) -> Self {
+        Self {
+            c: array[0],
+            m: array[1],
+            y: array[2],
+            k: array[3],
+        }
+    }
+}
+
+impl From<Cmyk> for [f64; 4] {
+    fn from(color: Cmyk) -> Self {
+        [color.c, color.m, color.y, color.k]
+    }
+}
+
+//
+// Implement From for all other Color types
+//
+
+impl From<Rgb> for Cmyk {
+    fn from(other: Rgb) -> Self {
+        let k = 1.0 - other.r.max(other.g).max(other.b);
+
+        // Pure black has no room left for the other inks, and would divide by zero.
+        if k >= 1.0 {
+            return Self {
+                c: 0.0,
+                m: 0.0,
+                y: 0.0,
+                k: 1.0,
+            };
+        }
+
+        Self {
+            c: (1.0 - other.r - k) / (1.0 - k),
+            m: (1.0 - other.g - k) / (1.0 - k),
+            y: (1.0 - other.b - k) / (1.0 - k),
+            k,
+        }
+    }
+}
+
+impl From<Cmyk> for Rgb {
+    fn from(other: Cmyk) -> Self {
+        Self {
+            r: (1.0 - other.c) * (1.0 - other.k),
+            g: (1.0 - other.m) * (1.0 - other.k),
+            b: (1.0 - other.y) * (1.0 - other.k),
+        }
+    }
+}
+
+impl_from_via!(Rgb: Rgba, Hsv, Hsva, Hsl, Hsla, Xyz, Oklab => Cmyk);
+impl_from_via!(Rgb: Cmyk => Rgba, Hsv, Hsva, Hsl, Hsla, Xyz, Oklab);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_clamped() {
+        let color = Cmyk::from([-0.1, 0.5, 1.2, 1.0]).clamped();
+
+        assert_eq!(<[f64; 4]>::from(color), [0.0, 0.5, 1.0, 1.0]);
+    }
+
+    #[test_case([0.0, 0.0, 0.0] => [0.0, 0.0, 0.0, 1.0] ; "black")]
+    #[test_case([1.0, 1.0, 1.0] => [0.0, 0.0, 0.0, 0.0] ; "white")]
+    #[test_case([1.0, 0.0, 0.0] => [0.0, 1.0, 1.0, 0.0] ; "red")]
+    #[test_case([0.0, 0.5, 0.5] => [1.0, 0.0, 0.0, 0.5] ; "dark cyan")]
+    fn test_rgb_to_cmyk(rgb: [f64; 3]) -> [f64; 4] {
+        Cmyk::from(Rgb::from(rgb)).into()
+    }
+
+    #[test_case([0.0, 0.0, 0.0] ; "black")]
+    #[test_case([0.2, 0.4, 0.6] ; "steel blue")]
+    #[test_case([0.9, 0.75, 0.01] ; "amber")]
+    fn test_rgb_cmyk_round_trip(rgb: [f64; 3]) {
+        let round_trip: [f64; 3] = Rgb::from(Cmyk::from(Rgb::from(rgb))).into();
+
+        for (expected, actual) in rgb.iter().zip(round_trip) {
+            assert!(
+                (expected - actual).abs() < 1e-12,
+                "{rgb:?} != {round_trip:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,152 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::xyz::multiply;
+use crate::{
+    gamut::{linear_to_srgb, srgb_to_linear},
+    types::*,
+};
+
+/// This structure represents colors in the Display P3 color space, which has the same
+/// white point and transfer function as sRGB, but wider primaries.
+/// See the [CSS Color 4 reference](<https://www.w3.org/TR/css-color-4/#predefined-display-p3>) for details.
+///
+/// Every color inside of sRGB is also inside of Display P3, but not the other way around.
+/// Conversions to and from [`Rgb`] go through [`Xyz`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DisplayP3 {
+    /// Red channel.
+    /// Ranged `0.0..1.0`.
+    pub r: f64,
+    /// Green channel.
+    /// Ranged `0.0..1.0`.
+    pub g: f64,
+    /// Blue channel.
+    /// Ranged `0.0..1.0`.
+    pub b: f64,
+}
+
+impl Eq for DisplayP3 {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for DisplayP3 {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.r.to_bits().hash(state);
+        self.g.to_bits().hash(state);
+        self.b.to_bits().hash(state);
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for DisplayP3 {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            r: array[0],
+            g: array[1],
+            b: array[2],
+        }
+    }
+}
+
+impl From<DisplayP3> for [f64; 3] {
+    fn from(color: DisplayP3) -> Self {
+        [color.r, color.g, color.b]
+    }
+}
+
+//
+// Implement to/from Xyz
+//
+
+impl From<Xyz> for DisplayP3 {
+    fn from(other: Xyz) -> Self {
+        let [r, g, b] = multiply(&XYZ_TO_P3, other.into());
+
+        Self {
+            r: linear_to_srgb(r),
+            g: linear_to_srgb(g),
+            b: linear_to_srgb(b),
+        }
+    }
+}
+
+impl From<DisplayP3> for Xyz {
+    fn from(other: DisplayP3) -> Self {
+        let linear = [
+            srgb_to_linear(other.r),
+            srgb_to_linear(other.g),
+            srgb_to_linear(other.b),
+        ];
+
+        Self::from(multiply(&P3_TO_XYZ, linear))
+    }
+}
+
+impl_from_via!(Xyz: Rgb, Rgba => DisplayP3);
+impl_from_via!(Xyz: DisplayP3 => Rgb, Rgba);
+
+//
+// Math helpers
+//
+
+/// Linear Display P3 to XYZ, derived from the P3 primaries and the D65 white point `(0.3127, 0.3290)`.
+const P3_TO_XYZ: [[f64; 3]; 3] = [
+    [0.48657094864821626, 0.26566769316909294, 0.1982172852343625],
+    [0.22897456406974884, 0.6917385218365062, 0.079286914093745],
+    [0.0, 0.045113381858902575, 1.0439443689009757],
+];
+
+/// The inverse of [`P3_TO_XYZ`].
+const XYZ_TO_P3: [[f64; 3]; 3] = [
+    [
+        2.4934969119414245,
+        -0.9313836179191236,
+        -0.40271078445071684,
+    ],
+    [-0.829488969561575, 1.7626640603183468, 0.02362468584194359],
+    [
+        0.035845830243784335,
+        -0.07617238926804171,
+        0.9568845240076873,
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_srgb_red_in_p3() {
+        let DisplayP3 { r, g, b } = DisplayP3::from(Rgb::from([1.0, 0.0, 0.0]));
+
+        // Reference values from the CSS Color 4 sample code
+        assert!((r - 0.9175).abs() < 1e-4, "r was {r}");
+        assert!((g - 0.2003).abs() < 1e-4, "g was {g}");
+        assert!((b - 0.1386).abs() < 1e-4, "b was {b}");
+    }
+
+    #[test]
+    fn test_white_is_unchanged() {
+        let white: [f64; 3] = DisplayP3::from(Rgb::from([1.0, 1.0, 1.0])).into();
+
+        for channel in white {
+            assert!((channel - 1.0).abs() < 1e-9, "{white:?}");
+        }
+    }
+}
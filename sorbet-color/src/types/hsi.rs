@@ -0,0 +1,157 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::types::*;
+
+/// This structure represents colors in the HSI color model with
+/// hue, saturation, and intensity channels, as used in image processing and computer vision.
+/// See the [Wikipedia reference](<https://en.wikipedia.org/wiki/HSL_and_HSV#HSI>) for details.
+///
+/// Unlike [`Hsv`] and [`Hsl`], the intensity is the mean of the red, green, and blue channels,
+/// and the hue is the angle from the red axis on the chromaticity plane, as given by
+/// Gonzalez and Woods in *Digital Image Processing*. Some colors that have a valid intensity
+/// and saturation are outside of the RGB cube, and convert to [`Rgb`] channels above `1.0`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Hsi {
+    /// Hue channel.
+    /// Ranged `0.0..360.0`.
+    pub h: f64,
+    /// Saturation channel.
+    /// Ranged `0.0..1.0`.
+    pub s: f64,
+    /// Intensity channel.
+    /// Ranged `0.0..1.0`.
+    pub i: f64,
+}
+
+impl Eq for Hsi {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for Hsi {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.h.to_bits().hash(state);
+        self.s.to_bits().hash(state);
+        self.i.to_bits().hash(state);
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for Hsi {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            h: array[0],
+            s: array[1],
+            i: array[2],
+        }
+    }
+}
+
+impl From<Hsi> for [f64; 3] {
+    fn from(color: Hsi) -> Self {
+        [color.h, color.s, color.i]
+    }
+}
+
+//
+// Implement From for all other Color types
+//
+
+impl From<Rgb> for Hsi {
+    fn from(other: Rgb) -> Self {
+        let Rgb { r, g, b } = other;
+        let i = (r + g + b) / 3.0;
+        let s = match () {
+            _ if i == 0.0 => 0.0,
+            _ => 1.0 - r.min(g).min(b) / i,
+        };
+        let denominator = ((r - g).powi(2) + (r - b) * (g - b)).sqrt();
+        let h = match () {
+            // Gray has no hue.
+            _ if denominator == 0.0 => 0.0,
+            _ => {
+                let theta = ((r - g + (r - b)) / 2.0 / denominator)
+                    .clamp(-1.0, 1.0)
+                    .acos()
+                    .to_degrees();
+
+                if b <= g {
+                    theta
+                } else {
+                    360.0 - theta
+                }
+            }
+        };
+
+        Self { h, s, i }
+    }
+}
+
+impl From<Hsi> for Rgb {
+    fn from(other: Hsi) -> Self {
+        let Hsi { h, s, i } = other;
+        let h = h.rem_euclid(360.0);
+        // Each 120 degree sector has the same formula, with the channels rotated.
+        let sector = (h / 120.0).floor();
+        let h = (h - sector * 120.0).to_radians();
+
+        let low = i * (1.0 - s);
+        let high = i * (1.0 + s * h.cos() / (std::f64::consts::FRAC_PI_3 - h).cos());
+        let rest = 3.0 * i - (low + high);
+
+        match sector as u8 {
+            0 => Self::from([high, rest, low]),
+            1 => Self::from([low, high, rest]),
+            _ => Self::from([rest, low, high]),
+        }
+    }
+}
+
+impl_from_via!(Rgb: Rgba, Hsv, Hsva, Hsl, Hsla => Hsi);
+impl_from_via!(Rgb: Hsi => Rgba, Hsv, Hsva, Hsl, Hsla);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case([1.0, 0.0, 0.0] => [0.0, 1.0, 0.3333] ; "red")]
+    #[test_case([0.0, 1.0, 0.0] => [120.0, 1.0, 0.3333] ; "green")]
+    #[test_case([0.0, 0.0, 1.0] => [240.0, 1.0, 0.3333] ; "blue")]
+    #[test_case([1.0, 1.0, 1.0] => [0.0, 0.0, 1.0] ; "white")]
+    #[test_case([0.0, 0.0, 0.0] => [0.0, 0.0, 0.0] ; "black")]
+    fn test_from_rgb(rgb: [f64; 3]) -> [f64; 3] {
+        <[f64; 3]>::from(Hsi::from(Rgb::from(rgb))).map(|x| (x * 1e4).round() / 1e4)
+    }
+
+    #[test_case([0.2, 0.4, 0.6] ; "steel blue")]
+    #[test_case([0.9, 0.7, 0.1] ; "gold")]
+    #[test_case([0.5, 0.1, 0.8] ; "purple")]
+    #[test_case([0.3, 0.3, 0.3] ; "gray")]
+    fn test_round_trip(channels: [f64; 3]) {
+        let rgb = Rgb::from(channels);
+        let round_trip = Rgb::from(rgb.to_hsi());
+
+        for (expected, actual) in channels.iter().zip(<[f64; 3]>::from(round_trip)) {
+            assert!(
+                (expected - actual).abs() < 1e-9,
+                "{rgb:?} != {round_trip:?}"
+            );
+        }
+    }
+}
@@ -14,7 +14,17 @@
  * limitations under the License.
  */
 
-use crate::{css, types::*, Color};
+use crate::{css, gamut::RenderingIntent, types::*, Color};
+
+/// The color space whose hue is rotated by [`Hsl::analogous_in`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum HueSpace {
+    /// The hue of [`Hsl`], which matches the color wheel of classic color theory.
+    #[default]
+    Hsl,
+    /// The hue of [`Oklch`], which spaces hues evenly as perceived and keeps the perceived lightness.
+    Oklch,
+}
 
 /// This structure represents colors in the HSL color space with
 /// hue, saturation, and lightness channels.
@@ -52,6 +62,183 @@ impl std::hash::Hash for Hsl {
     }
 }
 
+impl Hsl {
+    /// Creates a color from the hue, saturation, and lightness channels as a `const fn`.
+    /// See [`Rgb::from_channels`].
+    pub const fn from_channels(h: f64, s: f64, l: f64) -> Self {
+        Self { h, s, l }
+    }
+
+    /// Returns the color with the hue wrapped into `0.0..360.0`, so that `-30.0` becomes `330.0`,
+    /// and every other channel clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        Self {
+            h: self.h.rem_euclid(360.0),
+            s: self.s.clamp(0.0, 1.0),
+            l: self.l.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns the color with the hue replaced by `h`, wrapped into `0.0..360.0`,
+    /// such as `color.with_hue(color.h + 30.0)`.
+    #[must_use]
+    pub fn with_hue(self, h: f64) -> Self {
+        Self {
+            h: h.rem_euclid(360.0),
+            ..self
+        }
+    }
+
+    /// Returns the color with the saturation channel replaced by `s`.
+    #[must_use]
+    pub fn with_saturation(self, s: f64) -> Self {
+        Self { s, ..self }
+    }
+
+    /// Returns the color with the lightness channel replaced by `l`.
+    #[must_use]
+    pub fn with_lightness(self, l: f64) -> Self {
+        Self { l, ..self }
+    }
+
+    /// Returns the color as [`Hsla`] with the alpha channel `alpha`, such as `color.with_alpha(0.5)`.
+    #[must_use]
+    pub fn with_alpha(self, alpha: f64) -> Hsla {
+        Hsla {
+            h: self.h,
+            s: self.s,
+            l: self.l,
+            alpha,
+        }
+    }
+
+    /// Returns the color in CSS functional notation, such as `hsl(210deg, 50%, 40%)`,
+    /// with an explicit `deg` unit on the hue so that it can not be mistaken for a fraction of a turn.
+    /// This can be parsed again by [`Color::new`] or [`str::parse`].
+    pub fn to_css(&self) -> String {
+        format!(
+            "hsl({}deg, {}%, {}%)",
+            css::float_to_nice_string(self.h),
+            css::float_to_nice_string(self.s * 100.0),
+            css::float_to_nice_string(self.l * 100.0),
+        )
+    }
+
+    /// Returns the `(x, y)` coordinates of this color on a unit-disc color wheel,
+    /// where the angle is the hue and the distance from the center is the saturation.
+    ///
+    /// A hue of `0.0` lies on the positive X-axis, and hue increases counter-clockwise
+    /// toward the positive Y-axis. If your screen-space Y-axis points down, negate `y`.
+    pub fn to_wheel(&self) -> (f64, f64) {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+
+        (self.s * cos, self.s * sin)
+    }
+
+    /// The inverse of [`Hsl::to_wheel`], this takes `(x, y)` coordinates on a unit-disc color wheel
+    /// and the lightness channel, and returns the color at that point.
+    ///
+    /// Points outside of the unit disc are clamped to its edge (a saturation of `1.0`).
+    /// The center of the wheel has no defined hue, so a hue of `0.0` is used.
+    pub fn from_wheel(x: f64, y: f64, lightness: f64) -> Self {
+        let s = x.hypot(y).min(1.0);
+        let h = match () {
+            _ if s == 0.0 => 0.0,
+            _ => y.atan2(x).to_degrees().rem_euclid(360.0),
+        };
+
+        Self { h, s, l: lightness }
+    }
+
+    /// Returns the color with `amount` added to the lightness channel, clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn lighten(self, amount: f64) -> Self {
+        Self {
+            l: (self.l + amount).clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Returns the color with `amount` subtracted from the lightness channel, clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn darken(self, amount: f64) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Returns the color with `amount` added to the saturation channel, clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn saturate(self, amount: f64) -> Self {
+        Self {
+            s: (self.s + amount).clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Returns the color with `amount` subtracted from the saturation channel, clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn desaturate(self, amount: f64) -> Self {
+        self.saturate(-amount)
+    }
+
+    /// Returns the color with `degrees` added to the hue, wrapped into `0.0..360.0`.
+    /// Negative degrees rotate the other way, so that rotating a hue of `10.0` by `-30.0` gives `340.0`.
+    #[must_use]
+    pub fn rotate_hue(self, degrees: f64) -> Self {
+        self.with_hue(self.h + degrees)
+    }
+
+    /// Returns the color with its hue rotated by a fraction `phase` of the color wheel,
+    /// so that driving `phase` from `0.0` to `1.0` with time animates a loop through every hue.
+    /// Values outside of `0.0..1.0` wrap around, and saturation and lightness are kept.
+    #[must_use]
+    pub fn hue_cycle(self, phase: f64) -> Self {
+        self.rotate_hue(phase.rem_euclid(1.0) * 360.0)
+    }
+
+    /// Returns the complementary color, on the opposite side of the color wheel.
+    /// For the complements of the painter's wheel, see [`Ryb::complement`].
+    #[must_use]
+    pub fn complement(self) -> Self {
+        self.rotate_hue(180.0)
+    }
+
+    /// Returns this color and the two colors a third of the color wheel away from it, in that order.
+    pub fn triad(self) -> [Self; 3] {
+        [self, self.rotate_hue(120.0), self.rotate_hue(240.0)]
+    }
+
+    /// Returns the color `spread` degrees before this one on the color wheel, this color,
+    /// and the color `spread` degrees after it, in that order. A `spread` of `30.0` is typical.
+    pub fn analogous(self, spread: f64) -> [Self; 3] {
+        [self.rotate_hue(-spread), self, self.rotate_hue(spread)]
+    }
+
+    /// Similar to [`Hsl::analogous`], but the neighboring hues are found in the color space `space`.
+    /// [`HueSpace::Hsl`] gives the same colors as [`Hsl::analogous`].
+    ///
+    /// With [`HueSpace::Oklch`] the neighbors keep the lightness and chroma of this color,
+    /// and are brought back into sRGB with [`RenderingIntent::PreserveHue`] when they fall outside of it.
+    pub fn analogous_in(self, spread: f64, space: HueSpace) -> [Self; 3] {
+        match space {
+            HueSpace::Hsl => self.analogous(spread),
+            HueSpace::Oklch => {
+                let oklch = Oklch::from(self);
+                let rotate = |degrees: f64| {
+                    let rotated = Oklch {
+                        h: (oklch.h + degrees).rem_euclid(360.0),
+                        ..oklch
+                    };
+
+                    Self::from(rotated.map_to_srgb(RenderingIntent::PreserveHue))
+                };
+
+                [rotate(-spread), self, rotate(spread)]
+            }
+        }
+    }
+}
+
 //
 // Implement to/from primitives
 //
@@ -108,7 +295,7 @@ impl From<Hsv> for Hsl {
         let l = other.v * (1.0 - (other.s / 2.0));
         let sl = match () {
             _ if l == 0.0 || l == 1.0 => 0.0,
-            _ => 2.0 * (1.0 - l / other.v),
+            _ => (other.v - l) / l.min(1.0 - l),
         };
 
         Self {
@@ -151,15 +338,21 @@ impl TryFrom<&css::CssColorNotation> for Hsl {
     fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
         match other.format {
             css::CssColorType::Hsl | css::CssColorType::Hsla => Ok(Self {
-                h: css::css_number_to_float(
-                    other.values.get(0).ok_or(css::Error::InvalidCssParams)?,
-                ) * 360.0,
-                s: css::css_number_to_float(
-                    other.values.get(1).ok_or(css::Error::InvalidCssParams)?,
-                ),
-                l: css::css_number_to_float(
-                    other.values.get(2).ok_or(css::Error::InvalidCssParams)?,
-                ),
+                h: other
+                    .values
+                    .get(0)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Hue),
+                s: other
+                    .values
+                    .get(1)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Unit),
+                l: other
+                    .values
+                    .get(2)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Unit),
             }),
             _ => Err(css::Error::WrongCssFormat),
         }
@@ -168,13 +361,102 @@ impl TryFrom<&css::CssColorNotation> for Hsl {
 
 impl From<Hsl> for css::CssColorNotation {
     fn from(other: Hsl) -> Self {
-        Self {
-            format: css::CssColorType::Hsv,
-            values: vec![
-                css::CssNumber::Float(other.h),
-                css::CssNumber::Percent(other.s),
-                css::CssNumber::Percent(other.l),
-            ],
+        Self::hsl(other.h, other.s, other.l)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(0.2 => 0.7 ; "lighten")]
+    #[test_case(-0.2 => 0.3 ; "darken")]
+    #[test_case(0.8 => 1.0 ; "clamped high")]
+    #[test_case(-0.8 => 0.0 ; "clamped low")]
+    fn test_lighten(amount: f64) -> f64 {
+        let l = Hsl::from([200.0, 0.5, 0.5]).lighten(amount).l;
+
+        (l * 1e12).round() / 1e12
+    }
+
+    #[test]
+    fn test_darken_and_desaturate() {
+        let color = Hsl::from([200.0, 0.5, 0.5]);
+
+        assert_eq!(color.darken(0.1), color.lighten(-0.1));
+        assert_eq!(color.desaturate(0.6), Hsl::from([200.0, 0.0, 0.5]));
+        assert_eq!(color.saturate(0.25), Hsl::from([200.0, 0.75, 0.5]));
+    }
+
+    #[test_case(30.0 => 20.0 ; "across the seam")]
+    #[test_case(-30.0 => 320.0 ; "negative")]
+    #[test_case(-750.0 => 320.0 ; "several turns")]
+    fn test_rotate_hue(degrees: f64) -> f64 {
+        Hsl::from([350.0, 0.5, 0.5]).rotate_hue(degrees).h
+    }
+
+    #[test_case(0.0 => 200.0 ; "start")]
+    #[test_case(0.5 => 20.0 ; "half")]
+    #[test_case(1.0 => 200.0 ; "full loop")]
+    #[test_case(1.25 => 290.0 ; "wraps")]
+    #[test_case(-0.25 => 110.0 ; "negative")]
+    fn test_hue_cycle(phase: f64) -> f64 {
+        let color = Hsl::from([200.0, 0.5, 0.5]).hue_cycle(phase);
+        assert_eq!((color.s, color.l), (0.5, 0.5));
+
+        color.h
+    }
+
+    #[test]
+    fn test_schemes() {
+        let color = Hsl::from([300.0, 0.5, 0.5]);
+        let hues = |colors: [Hsl; 3]| colors.map(|color| color.h);
+
+        assert_eq!(color.complement().h, 120.0);
+        assert_eq!(hues(color.triad()), [300.0, 60.0, 180.0]);
+        assert_eq!(hues(color.analogous(75.0)), [225.0, 300.0, 15.0]);
+    }
+
+    #[test]
+    fn test_analogous_in() {
+        let yellow = Hsl::from([60.0, 1.0, 0.5]);
+        let hsl = yellow.analogous_in(30.0, HueSpace::Hsl);
+        let oklch = yellow.analogous_in(30.0, HueSpace::Oklch);
+
+        assert_eq!(hsl, yellow.analogous(30.0));
+        assert_eq!(oklch[1], yellow);
+        assert_ne!(oklch[0].hex(), hsl[0].hex());
+        assert_ne!(oklch[2].hex(), hsl[2].hex());
+
+        // The neighbors in Oklch keep the perceived lightness of the base.
+        let lightness = |color: Hsl| Oklch::from(color).l;
+        for color in oklch {
+            assert!((lightness(color) - lightness(yellow)).abs() < 1e-6);
+        }
+    }
+
+    #[test_case([210.0, 0.5, 0.4] ; "steel blue")]
+    #[test_case([0.0, 1.0, 0.5] ; "red")]
+    #[test_case([359.5, 0.25, 0.75] ; "near seam")]
+    #[test_case([120.125, 0.333, 0.667] ; "fractional")]
+    fn test_to_css_round_trip(channels: [f64; 3]) {
+        let color = Hsl::from(channels);
+        let parsed = Hsl::new(color.to_css()).unwrap();
+
+        for (expected, actual) in channels.iter().zip(<[f64; 3]>::from(parsed)) {
+            assert!(
+                (expected - actual).abs() < 1e-3,
+                "{} parsed as {parsed:?}",
+                color.to_css()
+            );
         }
     }
+
+    #[test]
+    fn test_display_round_trip() {
+        let color = Hsl::from([210.0, 0.5, 0.4]);
+
+        assert_eq!(color.to_string().parse::<Hsl>().unwrap(), color);
+    }
 }
@@ -55,6 +55,53 @@ impl std::hash::Hash for Hsla {
     }
 }
 
+impl Hsla {
+    /// Creates a color from the hue, saturation, lightness, and alpha channels as a `const fn`.
+    /// See [`Rgb::from_channels`].
+    pub const fn from_channels(h: f64, s: f64, l: f64, alpha: f64) -> Self {
+        Self { h, s, l, alpha }
+    }
+
+    /// Returns the color with the hue wrapped and every other channel clamped into range.
+    /// See [`Hsl::clamped`].
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        Self {
+            h: self.h.rem_euclid(360.0),
+            s: self.s.clamp(0.0, 1.0),
+            l: self.l.clamp(0.0, 1.0),
+            alpha: self.alpha.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns the color with the hue replaced by `h`, wrapped into range. See [`Hsl::with_hue`].
+    #[must_use]
+    pub fn with_hue(self, h: f64) -> Self {
+        Self {
+            h: h.rem_euclid(360.0),
+            ..self
+        }
+    }
+
+    /// Returns the color with the saturation channel replaced by `s`.
+    #[must_use]
+    pub fn with_saturation(self, s: f64) -> Self {
+        Self { s, ..self }
+    }
+
+    /// Returns the color with the lightness channel replaced by `l`.
+    #[must_use]
+    pub fn with_lightness(self, l: f64) -> Self {
+        Self { l, ..self }
+    }
+
+    /// Returns the color with the alpha channel replaced by `alpha`, such as `color.with_alpha(0.5)`.
+    #[must_use]
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Self { alpha, ..self }
+    }
+}
+
 //
 // Implement to/from primitives
 //
@@ -63,8 +110,8 @@ impl From<[f64; 4]> for Hsla {
     fn from(array: [f64; 4]) -> Self {
         Self {
             h: array[0],
-            l: array[1],
-            s: array[2],
+            s: array[1],
+            l: array[2],
             alpha: array[3],
         }
     }
@@ -138,16 +185,10 @@ impl TryFrom<&css::CssColorNotation> for Hsla {
 
     fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
         match other.format {
-            css::CssColorType::Hsl => Ok(Self::from(Hsl::try_from(other)?)),
-            css::CssColorType::Hsla => {
-                let mut this = Self::from(Hsl::try_from(other)?);
-
-                this.alpha = css::css_number_to_float(
-                    other.values.get(3).ok_or(css::Error::InvalidCssParams)?,
-                );
-
-                Ok(this)
-            }
+            css::CssColorType::Hsl | css::CssColorType::Hsla => Ok(Self {
+                alpha: css::css_optional_alpha(other),
+                ..Self::from(Hsl::try_from(other)?)
+            }),
             _ => Err(css::Error::WrongCssFormat),
         }
     }
@@ -155,14 +196,28 @@ impl TryFrom<&css::CssColorNotation> for Hsla {
 
 impl From<Hsla> for css::CssColorNotation {
     fn from(other: Hsla) -> Self {
-        Self {
-            format: css::CssColorType::Hsla,
-            values: vec![
-                css::CssNumber::Float(other.h),
-                css::CssNumber::Percent(other.s),
-                css::CssNumber::Percent(other.l),
-                css::CssNumber::Percent(other.alpha),
-            ],
-        }
+        Self::hsla(other.h, other.s, other.l, other.alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case([-30.0, 1.5, -0.5, 2.0] => [330.0, 1.0, 0.0, 1.0] ; "negative hue")]
+    #[test_case([720.0, 0.5, 0.5, 0.5] => [0.0, 0.5, 0.5, 0.5] ; "two turns")]
+    #[test_case([400.0, 0.0, 1.0, -1.0] => [40.0, 0.0, 1.0, 0.0] ; "over one turn")]
+    fn test_clamped(channels: [f64; 4]) -> [f64; 4] {
+        Hsla::from(channels).clamped().into()
+    }
+
+    #[test]
+    fn test_array_round_trip() {
+        let array = [210.0, 0.5, 0.4, 0.75];
+        let color = Hsla::from(array);
+
+        assert_eq!((color.s, color.l), (0.5, 0.4));
+        assert_eq!(<[f64; 4]>::from(color), array);
     }
 }
@@ -52,6 +52,114 @@ impl std::hash::Hash for Hsv {
     }
 }
 
+impl Hsv {
+    /// Creates a color from the hue, saturation, and value channels as a `const fn`.
+    /// See [`Rgb::from_channels`].
+    pub const fn from_channels(h: f64, s: f64, v: f64) -> Self {
+        Self { h, s, v }
+    }
+
+    /// Returns the color with the hue wrapped and every other channel clamped into range.
+    /// See [`Hsl::clamped`].
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        Self {
+            h: self.h.rem_euclid(360.0),
+            s: self.s.clamp(0.0, 1.0),
+            v: self.v.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns the color with the hue replaced by `h`, wrapped into range. See [`Hsl::with_hue`].
+    #[must_use]
+    pub fn with_hue(self, h: f64) -> Self {
+        Self {
+            h: h.rem_euclid(360.0),
+            ..self
+        }
+    }
+
+    /// Returns the color with the saturation channel replaced by `s`.
+    #[must_use]
+    pub fn with_saturation(self, s: f64) -> Self {
+        Self { s, ..self }
+    }
+
+    /// Returns the color with the value channel replaced by `v`.
+    #[must_use]
+    pub fn with_value(self, v: f64) -> Self {
+        Self { v, ..self }
+    }
+
+    /// Returns the color as [`Hsva`] with the alpha channel `alpha`, such as `color.with_alpha(0.5)`.
+    #[must_use]
+    pub fn with_alpha(self, alpha: f64) -> Hsva {
+        Hsva {
+            h: self.h,
+            s: self.s,
+            v: self.v,
+            alpha,
+        }
+    }
+
+    /// Returns the color with `degrees` added to the hue, wrapped into `0.0..360.0`.
+    /// See [`Hsl::rotate_hue`].
+    #[must_use]
+    pub fn rotate_hue(self, degrees: f64) -> Self {
+        self.with_hue(self.h + degrees)
+    }
+
+    /// Returns the complementary color, on the opposite side of the color wheel.
+    #[must_use]
+    pub fn complement(self) -> Self {
+        self.rotate_hue(180.0)
+    }
+
+    /// Returns this color and the two colors a third of the color wheel away from it.
+    /// See [`Hsl::triad`].
+    pub fn triad(self) -> [Self; 3] {
+        [self, self.rotate_hue(120.0), self.rotate_hue(240.0)]
+    }
+
+    /// Returns this color between the colors `spread` degrees to either side of it.
+    /// See [`Hsl::analogous`].
+    pub fn analogous(self, spread: f64) -> [Self; 3] {
+        [self.rotate_hue(-spread), self, self.rotate_hue(spread)]
+    }
+
+    /// Returns the color in CSS functional notation, such as `hsv(210deg, 50%, 40%)`.
+    /// See [`Hsl::to_css`].
+    pub fn to_css(&self) -> String {
+        format!(
+            "hsv({}deg, {}%, {}%)",
+            css::float_to_nice_string(self.h),
+            css::float_to_nice_string(self.s * 100.0),
+            css::float_to_nice_string(self.v * 100.0),
+        )
+    }
+
+    /// Returns the `(x, y)` coordinates of this color on a unit-disc color wheel,
+    /// where the angle is the hue and the distance from the center is the saturation.
+    /// See [`Hsl::to_wheel`].
+    pub fn to_wheel(&self) -> (f64, f64) {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+
+        (self.s * cos, self.s * sin)
+    }
+
+    /// The inverse of [`Hsv::to_wheel`], this takes `(x, y)` coordinates on a unit-disc color wheel
+    /// and the value channel, and returns the color at that point. See [`Hsl::from_wheel`].
+    pub fn from_wheel(x: f64, y: f64, value: f64) -> Self {
+        let s = x.hypot(y).min(1.0);
+        let h = match () {
+            _ if s == 0.0 => 0.0,
+            _ => y.atan2(x).to_degrees().rem_euclid(360.0),
+        };
+
+        Self { h, s, v: value }
+    }
+}
+
 //
 // Implement to/from primitives
 //
@@ -150,15 +258,21 @@ impl TryFrom<&css::CssColorNotation> for Hsv {
     fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
         match other.format {
             css::CssColorType::Hsv | css::CssColorType::Hsva => Ok(Self {
-                h: css::css_number_to_float(
-                    other.values.get(0).ok_or(css::Error::InvalidCssParams)?,
-                ) * 360.0,
-                s: css::css_number_to_float(
-                    other.values.get(1).ok_or(css::Error::InvalidCssParams)?,
-                ),
-                v: css::css_number_to_float(
-                    other.values.get(2).ok_or(css::Error::InvalidCssParams)?,
-                ),
+                h: other
+                    .values
+                    .get(0)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Hue),
+                s: other
+                    .values
+                    .get(1)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Unit),
+                v: other
+                    .values
+                    .get(2)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Unit),
             }),
             _ => Err(css::Error::WrongCssFormat),
         }
@@ -167,13 +281,81 @@ impl TryFrom<&css::CssColorNotation> for Hsv {
 
 impl From<Hsv> for css::CssColorNotation {
     fn from(other: Hsv) -> Self {
-        Self {
-            format: css::CssColorType::Hsv,
-            values: vec![
-                css::CssNumber::Float(other.h),
-                css::CssNumber::Percent(other.s),
-                css::CssNumber::Percent(other.v),
-            ],
+        Self::hsv(other.h, other.s, other.v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(30.0 => 20.0 ; "across the seam")]
+    #[test_case(-30.0 => 320.0 ; "negative")]
+    #[test_case(-750.0 => 320.0 ; "several turns")]
+    fn test_rotate_hue(degrees: f64) -> f64 {
+        Hsv::from([350.0, 0.5, 0.5]).rotate_hue(degrees).h
+    }
+
+    #[test]
+    fn test_schemes() {
+        let color = Hsv::from([300.0, 0.5, 0.5]);
+        let hues = |colors: [Hsv; 3]| colors.map(|color| color.h);
+
+        assert_eq!(color.complement(), Hsv::from([120.0, 0.5, 0.5]));
+        assert_eq!(hues(color.triad()), [300.0, 60.0, 180.0]);
+        assert_eq!(hues(color.analogous(75.0)), [225.0, 300.0, 15.0]);
+    }
+
+    #[test]
+    fn test_from_wheel_positive_x() {
+        assert_eq!(Hsv::from_wheel(1.0, 0.0, 1.0), Hsv::from([0.0, 1.0, 1.0]));
+    }
+
+    #[test]
+    fn test_from_wheel_center() {
+        assert_eq!(Hsv::from_wheel(0.0, 0.0, 0.5), Hsv::from([0.0, 0.0, 0.5]));
+    }
+
+    #[test]
+    fn test_from_wheel_clamps_radius() {
+        let color = Hsv::from_wheel(0.0, -3.0, 1.0);
+
+        assert_eq!(color.s, 1.0);
+        assert_eq!(color.h, 270.0);
+    }
+
+    #[test]
+    fn test_wheel_round_trip() {
+        let color = Hsv::from([135.0, 0.6, 0.8]);
+        let (x, y) = color.to_wheel();
+        let other = Hsv::from_wheel(x, y, color.v);
+
+        assert!((other.h - color.h).abs() < 1e-9);
+        assert!((other.s - color.s).abs() < 1e-9);
+    }
+
+    #[test_case([210.0, 0.5, 0.4] ; "steel blue")]
+    #[test_case([0.0, 1.0, 0.5] ; "red")]
+    #[test_case([359.5, 0.25, 0.75] ; "near seam")]
+    #[test_case([120.125, 0.333, 0.667] ; "fractional")]
+    fn test_to_css_round_trip(channels: [f64; 3]) {
+        let color = Hsv::from(channels);
+        let parsed = Hsv::new(color.to_css()).unwrap();
+
+        for (expected, actual) in channels.iter().zip(<[f64; 3]>::from(parsed)) {
+            assert!(
+                (expected - actual).abs() < 1e-3,
+                "{} parsed as {parsed:?}",
+                color.to_css()
+            );
         }
     }
+
+    #[test]
+    fn test_display_round_trip() {
+        let color = Hsv::from([210.0, 0.5, 0.4]);
+
+        assert_eq!(color.to_string().parse::<Hsv>().unwrap(), color);
+    }
 }
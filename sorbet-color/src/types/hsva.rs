@@ -55,6 +55,53 @@ impl std::hash::Hash for Hsva {
     }
 }
 
+impl Hsva {
+    /// Creates a color from the hue, saturation, value, and alpha channels as a `const fn`.
+    /// See [`Rgb::from_channels`].
+    pub const fn from_channels(h: f64, s: f64, v: f64, alpha: f64) -> Self {
+        Self { h, s, v, alpha }
+    }
+
+    /// Returns the color with the hue wrapped and every other channel clamped into range.
+    /// See [`Hsl::clamped`].
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        Self {
+            h: self.h.rem_euclid(360.0),
+            s: self.s.clamp(0.0, 1.0),
+            v: self.v.clamp(0.0, 1.0),
+            alpha: self.alpha.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns the color with the hue replaced by `h`, wrapped into range. See [`Hsl::with_hue`].
+    #[must_use]
+    pub fn with_hue(self, h: f64) -> Self {
+        Self {
+            h: h.rem_euclid(360.0),
+            ..self
+        }
+    }
+
+    /// Returns the color with the saturation channel replaced by `s`.
+    #[must_use]
+    pub fn with_saturation(self, s: f64) -> Self {
+        Self { s, ..self }
+    }
+
+    /// Returns the color with the value channel replaced by `v`.
+    #[must_use]
+    pub fn with_value(self, v: f64) -> Self {
+        Self { v, ..self }
+    }
+
+    /// Returns the color with the alpha channel replaced by `alpha`, such as `color.with_alpha(0.5)`.
+    #[must_use]
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Self { alpha, ..self }
+    }
+}
+
 //
 // Implement to/from primitives
 //
@@ -138,16 +185,10 @@ impl TryFrom<&css::CssColorNotation> for Hsva {
 
     fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
         match other.format {
-            css::CssColorType::Hsv => Ok(Self::from(Hsv::try_from(other)?)),
-            css::CssColorType::Hsva => {
-                let mut this = Self::from(Hsv::try_from(other)?);
-
-                this.alpha = css::css_number_to_float(
-                    other.values.get(3).ok_or(css::Error::InvalidCssParams)?,
-                );
-
-                Ok(this)
-            }
+            css::CssColorType::Hsv | css::CssColorType::Hsva => Ok(Self {
+                alpha: css::css_optional_alpha(other),
+                ..Self::from(Hsv::try_from(other)?)
+            }),
             _ => Err(css::Error::WrongCssFormat),
         }
     }
@@ -155,14 +196,6 @@ impl TryFrom<&css::CssColorNotation> for Hsva {
 
 impl From<Hsva> for css::CssColorNotation {
     fn from(other: Hsva) -> Self {
-        Self {
-            format: css::CssColorType::Hsva,
-            values: vec![
-                css::CssNumber::Float(other.h),
-                css::CssNumber::Percent(other.s),
-                css::CssNumber::Percent(other.v),
-                css::CssNumber::Percent(other.alpha),
-            ],
-        }
+        Self::hsva(other.h, other.s, other.v, other.alpha)
     }
 }
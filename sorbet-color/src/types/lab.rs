@@ -0,0 +1,208 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{adaptation::WhitePoint, css, types::*};
+
+/// This structure represents colors in the CIE 1976 L\*a\*b\* color space,
+/// relative to the [`WhitePoint::D65`] white point.
+/// See the [Wikipedia reference](<https://en.wikipedia.org/wiki/CIELAB_color_space>) for details.
+///
+/// Conversions to and from [`Rgb`] and [`Rgba`] go through [`Xyz`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Lab {
+    /// Perceptual lightness channel.
+    /// Ranged `0.0..100.0`.
+    pub l: f64,
+    /// Green-red opponent channel, negative toward green.
+    /// Ranged roughly `-128.0..127.0`.
+    pub a: f64,
+    /// Blue-yellow opponent channel, negative toward blue.
+    /// Ranged roughly `-128.0..127.0`.
+    pub b: f64,
+}
+
+impl Eq for Lab {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for Lab {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.l.to_bits().hash(state);
+        self.a.to_bits().hash(state);
+        self.b.to_bits().hash(state);
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for Lab {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            l: array[0],
+            a: array[1],
+            b: array[2],
+        }
+    }
+}
+
+impl From<Lab> for [f64; 3] {
+    fn from(color: Lab) -> Self {
+        [color.l, color.a, color.b]
+    }
+}
+
+//
+// Implement to/from Xyz
+//
+
+impl From<Xyz> for Lab {
+    fn from(other: Xyz) -> Self {
+        let Xyz {
+            x: xn,
+            y: yn,
+            z: zn,
+        } = WhitePoint::D65.xyz();
+        let (fx, fy, fz) = (f(other.x / xn), f(other.y / yn), f(other.z / zn));
+
+        Self {
+            l: 116.0 * fy - 16.0,
+            a: 500.0 * (fx - fy),
+            b: 200.0 * (fy - fz),
+        }
+    }
+}
+
+impl From<Lab> for Xyz {
+    fn from(other: Lab) -> Self {
+        let Xyz {
+            x: xn,
+            y: yn,
+            z: zn,
+        } = WhitePoint::D65.xyz();
+        let fy = (other.l + 16.0) / 116.0;
+        let fx = fy + other.a / 500.0;
+        let fz = fy - other.b / 200.0;
+
+        Self {
+            x: xn * f_inv(fx),
+            y: yn * f_inv(fy),
+            z: zn * f_inv(fz),
+        }
+    }
+}
+
+impl_from_via!(Xyz: Rgb, Rgba => Lab);
+impl_from_via!(Xyz: Lab => Rgb, Rgba);
+
+//
+// Implement to/from CssColorNotation
+//
+
+impl TryFrom<&css::CssColorNotation> for Lab {
+    type Error = css::Error;
+
+    fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
+        match other.format {
+            css::CssColorType::Lab => Ok(Self {
+                l: css::css_number_to_scaled(
+                    other.values.get(0).ok_or(css::Error::InvalidCssParams)?,
+                    100.0,
+                ),
+                a: css::css_number_to_scaled(
+                    other.values.get(1).ok_or(css::Error::InvalidCssParams)?,
+                    125.0,
+                ),
+                b: css::css_number_to_scaled(
+                    other.values.get(2).ok_or(css::Error::InvalidCssParams)?,
+                    125.0,
+                ),
+            }),
+            _ => Err(css::Error::WrongCssFormat),
+        }
+    }
+}
+
+impl From<Lab> for css::CssColorNotation {
+    fn from(other: Lab) -> Self {
+        Self::lab(other.l, other.a, other.b)
+    }
+}
+
+//
+// Math helpers
+//
+
+/// The value of `t` where the transfer function switches from the linear segment to the cube root.
+const DELTA: f64 = 6.0 / 29.0;
+
+fn f(t: f64) -> f64 {
+    if t > DELTA.powi(3) {
+        t.cbrt()
+    } else {
+        t / (3.0 * DELTA.powi(2)) + 4.0 / 29.0
+    }
+}
+
+fn f_inv(t: f64) -> f64 {
+    if t > DELTA {
+        t.powi(3)
+    } else {
+        3.0 * DELTA.powi(2) * (t - 4.0 / 29.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case([0.0, 0.0, 0.0] ; "black")]
+    #[test_case([1.0, 1.0, 1.0] ; "white")]
+    #[test_case([0.0, 0.0, 1.0] ; "blue")]
+    #[test_case([0.2, 0.4, 0.6] ; "steel blue")]
+    #[test_case([0.01, 0.02, 0.005] ; "near black")]
+    fn test_rgb_lab_round_trip(rgb: [f64; 3]) {
+        let round_trip: [f64; 3] = Rgb::from(Lab::from(Rgb::from(rgb))).into();
+
+        for (expected, actual) in rgb.iter().zip(round_trip) {
+            assert!(
+                (expected - actual).abs() < 1e-6,
+                "{rgb:?} != {round_trip:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_white_and_black() {
+        let white = Lab::from(Rgb::from([1.0, 1.0, 1.0]));
+        let black = Lab::from(Rgb::from([0.0, 0.0, 0.0]));
+
+        assert!((white.l - 100.0).abs() < 1e-9, "{white:?}");
+        assert!(white.a.abs() < 1e-9 && white.b.abs() < 1e-9, "{white:?}");
+        assert_eq!(black, Lab::from([0.0, 0.0, 0.0]));
+    }
+
+    // Reference values for sRGB red under D65
+    #[test]
+    fn test_red() {
+        let Lab { l, a, b } = Lab::from(Rgba::from([1.0, 0.0, 0.0, 1.0]));
+
+        assert!((l - 53.24).abs() < 0.01, "l was {l}");
+        assert!((a - 80.09).abs() < 0.01, "a was {a}");
+        assert!((b - 67.20).abs() < 0.01, "b was {b}");
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{css, types::*};
+
+/// This structure represents colors in the polar form of [`Lab`],
+/// with lightness, chroma, and hue channels.
+/// See the [Wikipedia reference](<https://en.wikipedia.org/wiki/CIELAB_color_space#Cylindrical_model>)
+/// for details.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Lch {
+    /// Perceptual lightness channel.
+    /// Ranged `0.0..100.0`.
+    pub l: f64,
+    /// Chroma channel, the distance from the neutral axis.
+    /// Ranged roughly `0.0..150.0`.
+    pub c: f64,
+    /// Hue channel.
+    /// Ranged `0.0..360.0`.
+    pub h: f64,
+}
+
+impl Eq for Lch {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for Lch {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.l.to_bits().hash(state);
+        self.c.to_bits().hash(state);
+        self.h.to_bits().hash(state);
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for Lch {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            l: array[0],
+            c: array[1],
+            h: array[2],
+        }
+    }
+}
+
+impl From<Lch> for [f64; 3] {
+    fn from(color: Lch) -> Self {
+        [color.l, color.c, color.h]
+    }
+}
+
+//
+// Implement to/from Lab
+//
+
+impl From<Lab> for Lch {
+    fn from(other: Lab) -> Self {
+        Self {
+            l: other.l,
+            c: other.a.hypot(other.b),
+            h: other.b.atan2(other.a).to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+
+impl From<Lch> for Lab {
+    fn from(other: Lch) -> Self {
+        let (sin, cos) = other.h.to_radians().sin_cos();
+
+        Self {
+            l: other.l,
+            a: other.c * cos,
+            b: other.c * sin,
+        }
+    }
+}
+
+impl_from_via!(Lab: Rgb, Rgba => Lch);
+impl_from_via!(Lab: Lch => Rgb, Rgba);
+
+//
+// Implement to/from CssColorNotation
+//
+
+impl TryFrom<&css::CssColorNotation> for Lch {
+    type Error = css::Error;
+
+    fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
+        match other.format {
+            css::CssColorType::Lch => Ok(Self {
+                l: css::css_number_to_scaled(
+                    other.values.get(0).ok_or(css::Error::InvalidCssParams)?,
+                    100.0,
+                ),
+                c: css::css_number_to_scaled(
+                    other.values.get(1).ok_or(css::Error::InvalidCssParams)?,
+                    150.0,
+                ),
+                h: other
+                    .values
+                    .get(2)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Hue),
+            }),
+            _ => Err(css::Error::WrongCssFormat),
+        }
+    }
+}
+
+impl From<Lch> for css::CssColorNotation {
+    fn from(other: Lch) -> Self {
+        Self::lch(other.l, other.c, other.h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lab_round_trip() {
+        let lab = Lab::from([53.0, -20.0, 40.0]);
+        let round_trip: [f64; 3] = Lab::from(Lch::from(lab)).into();
+
+        for (expected, actual) in [53.0, -20.0, 40.0].iter().zip(round_trip) {
+            assert!((expected - actual).abs() < 1e-12, "{round_trip:?}");
+        }
+    }
+}
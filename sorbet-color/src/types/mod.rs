@@ -17,11 +17,93 @@
 //! This module contains all of the structures for the color-spaces and formats supported by the crate.
 //! They are re-exported in the crate-root.
 
+/// Implements [`From`] between color types that have no direct conversion,
+/// by converting through an intermediate type that both can convert to and from.
+///
+/// `impl_from_via!(Rgb: Rgba, Hsv => Xyz)` implements `From<Rgba>` and `From<Hsv>` for `Xyz`,
+/// whereas `impl_from_via!(Rgb: Xyz => Rgba, Hsv)` implements `From<Xyz>` for `Rgba` and `Hsv`.
+macro_rules! impl_from_via {
+    ( $via:ident: $( $from:ident ),+ => $to:ident ) => {
+        $(
+            impl From<$from> for $to {
+                fn from(other: $from) -> Self {
+                    Self::from($via::from(other))
+                }
+            }
+        )+
+    };
+    ( $via:ident: $from:ident => $( $to:ident ),+ ) => {
+        $(
+            impl From<$from> for $to {
+                fn from(other: $from) -> Self {
+                    Self::from($via::from(other))
+                }
+            }
+        )+
+    };
+}
+
+/// Implements `component_values`, `with_channels`, and [`IntoIterator`] for color types that convert
+/// to and from an array of their channels, and have a `clamped` method to bring the channels into range.
+///
+/// `impl_channels!(Rgb: 3, Rgba: 4)` uses `[f64; 3]` for `Rgb` and `[f64; 4]` for `Rgba`.
+macro_rules! impl_channels {
+    ( $( $t:ident: $n:literal ),+ ) => {
+        $(
+            impl $t {
+                /// Returns the channels of the color in the order of the fields of the structure.
+                pub fn component_values(&self) -> [f64; $n] {
+                    (*self).into()
+                }
+
+                /// The inverse of [`Self::component_values`], this returns a color with every channel
+                /// replaced from `values`, such as those handed back by a set of UI sliders.
+                /// The hue, if any, is wrapped and the other channels are clamped (see [`Self::clamped`]).
+                ///
+                /// Returns [`None`] if the length of `values` is not the number of channels.
+                pub fn with_channels(self, values: &[f64]) -> Option<Self> {
+                    Some(Self::from(<[f64; $n]>::try_from(values).ok()?).clamped())
+                }
+            }
+
+            /// Iterates over the channels of the color, in the same order as [`Self::component_values`].
+            impl IntoIterator for $t {
+                type Item = f64;
+                type IntoIter = std::array::IntoIter<f64, $n>;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    self.component_values().into_iter()
+                }
+            }
+        )+
+    };
+}
+
+impl_channels!(Rgb: 3, Rgba: 4, Hsv: 3, Hsva: 4, Hsl: 3, Hsla: 4, Cmyk: 4);
+
+mod any;
+mod cmyk;
+mod display_p3;
+mod hsi;
 mod hsl;
 mod hsla;
 mod hsv;
 mod hsva;
+mod lab;
+mod lch;
+mod okhsl;
+mod oklab;
+mod oklch;
+mod premul_rgba;
 mod rgb;
 mod rgba;
+mod ryb;
+mod xyz;
+mod ycbcr;
+mod yuv;
 
-pub use {hsl::*, hsla::*, hsv::*, hsva::*, rgb::*, rgba::*};
+pub use {
+    any::*, cmyk::*, display_p3::*, hsi::*, hsl::*, hsla::*, hsv::*, hsva::*, lab::*, lch::*,
+    okhsl::*, oklab::*, oklch::*, premul_rgba::*, rgb::*, rgba::*, ryb::*, xyz::*, ycbcr::*,
+    yuv::*,
+};
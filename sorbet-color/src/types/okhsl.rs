@@ -0,0 +1,440 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{types::*, Color};
+
+/// Colors with a saturation below this are considered achromatic by [`mix_okhsl`],
+/// so their hue is ignored.
+const ACHROMATIC_SATURATION: f64 = 1e-4;
+
+/// The saturation at which the chroma reaches the middle of the hue's range.
+/// Below it the chroma is scaled from zero, and above it toward the edge of the sRGB gamut.
+const MID: f64 = 0.8;
+
+/// This structure represents colors in Björn Ottosson's Okhsl color space, with
+/// hue, saturation, and lightness channels. See the [reference post](<https://bottosson.github.io/posts/colorpicker/>)
+/// for details.
+///
+/// Okhsl is built on [`Oklab`] in the same shape as [`Hsl`], so that every combination of
+/// the channels is in the sRGB gamut, but the lightness is perceptually uniform
+/// and the hue is the same as that of [`Oklch`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Okhsl {
+    /// Hue channel.
+    /// Ranged `0.0..360.0`.
+    pub h: f64,
+    /// Saturation channel, relative to the most saturated color in sRGB with this hue and lightness.
+    /// Ranged `0.0..1.0`.
+    pub s: f64,
+    /// Perceptual lightness channel.
+    /// Ranged `0.0..1.0`.
+    pub l: f64,
+}
+
+impl Eq for Okhsl {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for Okhsl {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.h.to_bits().hash(state);
+        self.s.to_bits().hash(state);
+        self.l.to_bits().hash(state);
+    }
+}
+
+impl Color for Okhsl {
+    fn hex(&self) -> String {
+        Rgb::from(*self).hex()
+    }
+}
+
+/// Interpolates between `a` and `b` in [`Okhsl`], where `t` of `0.0` is `a` and `1.0` is `b`.
+/// The `t` is clamped to `0.0..=1.0`.
+///
+/// Saturation and lightness are interpolated linearly, and hue takes the shortest path
+/// around the hue circle. As with [`mix_oklch`], if one of the colors is achromatic,
+/// the hue of the other color is used for the whole mix.
+pub fn mix_okhsl(a: Okhsl, b: Okhsl, t: f64) -> Okhsl {
+    let t = t.clamp(0.0, 1.0);
+
+    let h = match (a.s < ACHROMATIC_SATURATION, b.s < ACHROMATIC_SATURATION) {
+        (true, false) => b.h,
+        (false, true) | (true, true) => a.h,
+        (false, false) => {
+            let delta = (b.h - a.h + 180.0).rem_euclid(360.0) - 180.0;
+
+            (a.h + delta * t).rem_euclid(360.0)
+        }
+    };
+
+    Okhsl {
+        h,
+        s: a.s + (b.s - a.s) * t,
+        l: a.l + (b.l - a.l) * t,
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for Okhsl {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            h: array[0],
+            s: array[1],
+            l: array[2],
+        }
+    }
+}
+
+impl From<Okhsl> for [f64; 3] {
+    fn from(color: Okhsl) -> Self {
+        [color.h, color.s, color.l]
+    }
+}
+
+//
+// Implement to/from Oklab
+//
+
+// https://bottosson.github.io/posts/colorpicker/#hsl-2
+impl From<Oklab> for Okhsl {
+    fn from(other: Oklab) -> Self {
+        let c = other.a.hypot(other.b);
+        let l = toe(other.l);
+
+        if c < 1e-9 || other.l <= 0.0 || other.l >= 1.0 {
+            return Self { h: 0.0, s: 0.0, l };
+        }
+
+        let (a, b) = (other.a / c, other.b / c);
+        let (c_0, c_mid, c_max) = chroma_stops(other.l, a, b);
+
+        let s = if c < c_mid {
+            let k_1 = MID * c_0;
+            let k_2 = 1.0 - k_1 / c_mid;
+
+            c / (k_1 + k_2 * c) * MID
+        } else {
+            let k_1 = (1.0 - MID) * c_mid * c_mid / (MID * MID * c_0);
+            let k_2 = 1.0 - k_1 / (c_max - c_mid);
+            let t = (c - c_mid) / (k_1 + k_2 * (c - c_mid));
+
+            MID + (1.0 - MID) * t
+        };
+
+        Self {
+            h: b.atan2(a).to_degrees().rem_euclid(360.0),
+            s,
+            l,
+        }
+    }
+}
+
+impl From<Okhsl> for Oklab {
+    fn from(other: Okhsl) -> Self {
+        let l = toe_inv(other.l);
+
+        if other.l <= 0.0 || other.l >= 1.0 || other.s <= 0.0 {
+            return Self { l, a: 0.0, b: 0.0 };
+        }
+
+        let (b, a) = other.h.to_radians().sin_cos();
+        let (c_0, c_mid, c_max) = chroma_stops(l, a, b);
+
+        let c = if other.s < MID {
+            let t = other.s / MID;
+            let k_1 = MID * c_0;
+            let k_2 = 1.0 - k_1 / c_mid;
+
+            t * k_1 / (1.0 - k_2 * t)
+        } else {
+            let t = (other.s - MID) / (1.0 - MID);
+            let k_1 = (1.0 - MID) * c_mid * c_mid / (MID * MID * c_0);
+            let k_2 = 1.0 - k_1 / (c_max - c_mid);
+
+            c_mid + t * k_1 / (1.0 - k_2 * t)
+        };
+
+        Self {
+            l,
+            a: c * a,
+            b: c * b,
+        }
+    }
+}
+
+impl_from_via!(Oklab: Rgb, Rgba => Okhsl);
+impl_from_via!(Oklab: Okhsl => Rgb, Rgba);
+impl_from_via!(Rgb: Hsv, Hsva, Hsl, Hsla, Xyz, Cmyk => Okhsl);
+impl_from_via!(Rgb: Okhsl => Hsv, Hsva, Hsl, Hsla, Xyz, Cmyk);
+
+//
+// The gamut approximations from the reference post, which find the chroma at the edge of
+// the sRGB gamut for a hue, given as the normalized `a` and `b` of an OKLab color.
+//
+
+/// Maps OKLab lightness to a lightness estimate closer to CIE Lab, with a darker toe.
+fn toe(x: f64) -> f64 {
+    const K_1: f64 = 0.206;
+    const K_2: f64 = 0.03;
+    const K_3: f64 = (1.0 + K_1) / (1.0 + K_2);
+
+    0.5 * (K_3 * x - K_1 + ((K_3 * x - K_1).powi(2) + 4.0 * K_2 * K_3 * x).sqrt())
+}
+
+/// The inverse of [`toe`].
+fn toe_inv(x: f64) -> f64 {
+    const K_1: f64 = 0.206;
+    const K_2: f64 = 0.03;
+    const K_3: f64 = (1.0 + K_1) / (1.0 + K_2);
+
+    (x * x + K_1 * x) / (K_3 * (x + K_2))
+}
+
+/// Returns the linear sRGB of an OKLab color, without gamma encoding.
+fn oklab_to_linear_srgb(l: f64, a: f64, b: f64) -> [f64; 3] {
+    let lms = lms_coefficients(a, b).map(|k| (l + k).powi(3));
+
+    LMS_TO_RGB.map(|w| w[0] * lms[0] + w[1] * lms[1] + w[2] * lms[2])
+}
+
+/// The coefficients of OKLab `a` and `b` for the cube roots of the LMS cone responses.
+fn lms_coefficients(a: f64, b: f64) -> [f64; 3] {
+    [
+        0.3963377774 * a + 0.2158037573 * b,
+        -0.1055613458 * a - 0.0638541728 * b,
+        -0.0894841775 * a - 1.2914855480 * b,
+    ]
+}
+
+/// The rows of the matrix from LMS to linear sRGB.
+const LMS_TO_RGB: [[f64; 3]; 3] = [
+    [4.0767416621, -3.3077115913, 0.2309699292],
+    [-1.2684380046, 2.6097574011, -0.3413193965],
+    [-0.0041960863, -0.7034186147, 1.7076147010],
+];
+
+/// Returns the largest saturation (`C / L`) that stays in the sRGB gamut for a hue.
+fn max_saturation(a: f64, b: f64) -> f64 {
+    // The polynomial depends on which channel goes below zero first.
+    let (k, w) = if -1.88170328 * a - 0.80936493 * b > 1.0 {
+        (
+            [1.19086277, 1.76576728, 0.59662641, 0.75515197, 0.56771245],
+            LMS_TO_RGB[0],
+        )
+    } else if 1.81444104 * a - 1.19445276 * b > 1.0 {
+        (
+            [0.73956515, -0.45954404, 0.08285427, 0.12541070, 0.14503204],
+            LMS_TO_RGB[1],
+        )
+    } else {
+        (
+            [
+                1.35733652,
+                -0.00915799,
+                -1.15130210,
+                -0.50559606,
+                0.00692167,
+            ],
+            LMS_TO_RGB[2],
+        )
+    };
+
+    let s = k[0] + k[1] * a + k[2] * b + k[3] * a * a + k[4] * a * b;
+
+    // One step of Halley's method to get closer.
+    let k_lms = lms_coefficients(a, b);
+    let (mut f, mut f_1, mut f_2) = (0.0, 0.0, 0.0);
+    for (k, w) in k_lms.into_iter().zip(w) {
+        let root = 1.0 + s * k;
+        f += w * root.powi(3);
+        f_1 += w * 3.0 * k * root.powi(2);
+        f_2 += w * 6.0 * k * k * root;
+    }
+
+    s - f * f_1 / (f_1 * f_1 - 0.5 * f * f_2)
+}
+
+/// Returns the lightness and chroma of the most saturated color in the sRGB gamut for a hue.
+fn find_cusp(a: f64, b: f64) -> (f64, f64) {
+    let s_cusp = max_saturation(a, b);
+    let [r, g, b] = oklab_to_linear_srgb(1.0, s_cusp * a, s_cusp * b);
+    let l_cusp = (1.0 / r.max(g).max(b)).cbrt();
+
+    (l_cusp, l_cusp * s_cusp)
+}
+
+/// Returns how far along the line from `(l_0, 0.0)` to `(l_1, c_1)` the edge of the sRGB gamut is,
+/// for a hue with the cusp `cusp`.
+fn find_gamut_intersection(a: f64, b: f64, l_1: f64, c_1: f64, l_0: f64, cusp: (f64, f64)) -> f64 {
+    let (l_cusp, c_cusp) = cusp;
+
+    if (l_1 - l_0) * c_cusp - (l_cusp - l_0) * c_1 <= 0.0 {
+        // The lower half of the gamut is a straight line to black.
+        return c_cusp * l_0 / (c_1 * l_cusp + c_cusp * (l_0 - l_1));
+    }
+
+    // The upper half is curved, so start from a straight line to white.
+    let t = c_cusp * (l_0 - 1.0) / (c_1 * (l_cusp - 1.0) + c_cusp * (l_0 - l_1));
+
+    // Then one step of Halley's method for each channel, taking the nearest.
+    let k_lms = lms_coefficients(a, b);
+    let l = l_0 * (1.0 - t) + t * l_1;
+    let c = t * c_1;
+
+    let step = LMS_TO_RGB
+        .iter()
+        .map(|w| {
+            let (mut f, mut f_1, mut f_2) = (-1.0, 0.0, 0.0);
+            for (k, w) in k_lms.into_iter().zip(w) {
+                let root = l + c * k;
+                let root_dt = (l_1 - l_0) + c_1 * k;
+                f += w * root.powi(3);
+                f_1 += w * 3.0 * root_dt * root.powi(2);
+                f_2 += w * 6.0 * root_dt.powi(2) * root;
+            }
+            let u = f_1 / (f_1 * f_1 - 0.5 * f * f_2);
+
+            if u >= 0.0 {
+                -f * u
+            } else {
+                f64::MAX
+            }
+        })
+        .fold(f64::MAX, f64::min);
+
+    t + step
+}
+
+/// Returns the chroma at a saturation of zero (extrapolated), at [`MID`], and at the edge of
+/// the sRGB gamut, for the OKLab lightness `l` and a hue.
+fn chroma_stops(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    let cusp = find_cusp(a, b);
+    let c_max = find_gamut_intersection(a, b, l, 1.0, l, cusp);
+    let (st_max_s, st_max_t) = (cusp.1 / cusp.0, cusp.1 / (1.0 - cusp.0));
+
+    // Scale factor to compensate for the curved part of the gamut shape.
+    let k = c_max / (l * st_max_s).min((1.0 - l) * st_max_t);
+
+    // A smooth approximation of the gamut shape, from the reference post.
+    let st_mid_s = 0.11516993
+        + 1.0
+            / (7.44778970
+                + 4.15901240 * b
+                + a * (-2.19557347
+                    + 1.75198401 * b
+                    + a * (-2.13704948 - 10.02301043 * b
+                        + a * (-4.24894561 + 5.38770819 * b + 4.69891013 * a))));
+    let st_mid_t = 0.11239642
+        + 1.0
+            / (1.61320320 - 0.68124379 * b
+                + a * (0.40370612
+                    + 0.90148123 * b
+                    + a * (-0.27087943
+                        + 0.61223990 * b
+                        + a * (0.00299215 - 0.45399568 * b - 0.14661872 * a))));
+
+    // Soft minimums, instead of the sharp triangle of the gamut, give a smooth chroma.
+    let c_mid = {
+        let (c_a, c_b) = (l * st_mid_s, (1.0 - l) * st_mid_t);
+
+        0.9 * k * (1.0 / (c_a.powi(-4) + c_b.powi(-4))).powf(0.25)
+    };
+    let c_0 = {
+        // The shape is independent of hue here, so these are roughly the average of the above.
+        let (c_a, c_b) = (l * 0.4, (1.0 - l) * 0.8);
+
+        (1.0 / (c_a.powi(-2) + c_b.powi(-2))).sqrt()
+    };
+
+    (c_0, c_mid, c_max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case([0.0, 0.0, 0.0] ; "black")]
+    #[test_case([1.0, 1.0, 1.0] ; "white")]
+    #[test_case([0.5, 0.5, 0.5] ; "gray")]
+    #[test_case([1.0, 0.0, 0.0] ; "red")]
+    #[test_case([0.0, 0.0, 1.0] ; "blue")]
+    #[test_case([0.2, 0.4, 0.6] ; "steel blue")]
+    #[test_case([0.9, 0.75, 0.01] ; "amber")]
+    fn test_rgb_round_trip(rgb: [f64; 3]) {
+        let okhsl = Okhsl::from(Rgb::from(rgb));
+        let round_trip: [f64; 3] = Rgb::from(okhsl).into();
+
+        for (expected, actual) in rgb.iter().zip(round_trip) {
+            assert!(
+                (expected - actual).abs() < 1e-4,
+                "{rgb:?} != {round_trip:?} through {okhsl:?}"
+            );
+        }
+    }
+
+    #[test_case([0.0, 1.0, 0.5] ; "red")]
+    #[test_case([120.0, 1.0, 0.5] ; "green")]
+    #[test_case([240.0, 1.0, 0.5] ; "blue")]
+    fn test_full_saturation_on_gamut_edge(hsl: [f64; 3]) {
+        let s = Okhsl::from(Hsl::from(hsl)).s;
+
+        assert!((s - 1.0).abs() < 1e-2, "s was {s}");
+    }
+
+    // Okhsl lightness approximates CIE Lab lightness, so equal steps should look equal,
+    // whereas HSL lightness steps are much larger toward white for a dark hue like blue.
+    #[test]
+    fn test_lightness_steps_are_uniform() {
+        let steps = |color: &dyn Fn(f64) -> Lab| {
+            let lightness = (1..10)
+                .map(|i| color(i as f64 / 10.0).l)
+                .collect::<Vec<_>>();
+            let deltas = lightness.windows(2).map(|pair| pair[1] - pair[0]);
+
+            deltas.fold((f64::MAX, f64::MIN), |(min, max), delta| {
+                (min.min(delta), max.max(delta))
+            })
+        };
+
+        let (okhsl_min, okhsl_max) = steps(&|l| Lab::from(Rgb::from(Okhsl::from([264.0, 1.0, l]))));
+        let (hsl_min, hsl_max) = steps(&|l| Lab::from(Rgb::from(Hsl::from([240.0, 1.0, l]))));
+
+        assert!(okhsl_max - okhsl_min < 2.0);
+        assert!(hsl_max - hsl_min > 10.0);
+    }
+
+    #[test]
+    fn test_mix_okhsl() {
+        let a = Okhsl::from([350.0, 0.2, 0.3]);
+        let b = Okhsl::from([30.0, 0.6, 0.7]);
+        let mixed = mix_okhsl(a, b, 0.5);
+
+        assert!((mixed.h - 10.0).abs() < 1e-9, "h was {}", mixed.h);
+        assert!((mixed.s - 0.4).abs() < 1e-9 && (mixed.l - 0.5).abs() < 1e-9);
+        assert_eq!(mix_okhsl(Okhsl::from([0.0, 0.0, 0.5]), b, 0.5).h, 30.0);
+    }
+
+    #[test]
+    fn test_hue_matches_oklch() {
+        let rgb = Rgb::from([0.2, 0.4, 0.6]);
+
+        assert!((Okhsl::from(rgb).h - Oklch::from(rgb).h).abs() < 1e-9);
+    }
+}
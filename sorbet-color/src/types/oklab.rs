@@ -0,0 +1,185 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    css,
+    gamut::{linear_to_srgb, srgb_to_linear},
+    types::*,
+    Color,
+};
+
+/// This structure represents colors in Björn Ottosson's perceptual OKLab color space.
+/// See the [reference post](<https://bottosson.github.io/posts/oklab/>) for details.
+///
+/// Distances and straight lines in OKLab are close to perceptually uniform,
+/// which makes it a good space for blending and building gradients.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Oklab {
+    /// Perceptual lightness channel.
+    /// Ranged `0.0..1.0`.
+    pub l: f64,
+    /// Green-red opponent channel, negative toward green.
+    /// Ranged roughly `-0.4..0.4`.
+    pub a: f64,
+    /// Blue-yellow opponent channel, negative toward blue.
+    /// Ranged roughly `-0.4..0.4`.
+    pub b: f64,
+}
+
+impl Eq for Oklab {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for Oklab {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.l.to_bits().hash(state);
+        self.a.to_bits().hash(state);
+        self.b.to_bits().hash(state);
+    }
+}
+
+impl Color for Oklab {
+    fn hex(&self) -> String {
+        Rgb::from(*self).hex()
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for Oklab {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            l: array[0],
+            a: array[1],
+            b: array[2],
+        }
+    }
+}
+
+impl From<Oklab> for [f64; 3] {
+    fn from(color: Oklab) -> Self {
+        [color.l, color.a, color.b]
+    }
+}
+
+//
+// Implement From for all other Color types
+//
+
+// https://bottosson.github.io/posts/oklab/#converting-from-linear-srgb-to-oklab
+impl From<Rgb> for Oklab {
+    fn from(other: Rgb) -> Self {
+        let (r, g, b) = (
+            srgb_to_linear(other.r),
+            srgb_to_linear(other.g),
+            srgb_to_linear(other.b),
+        );
+
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        Self {
+            l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        }
+    }
+}
+
+impl From<Oklab> for Rgb {
+    fn from(other: Oklab) -> Self {
+        let Oklab { l, a, b } = other;
+
+        let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+        Self {
+            r: linear_to_srgb(4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_),
+            g: linear_to_srgb(-1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_),
+            b: linear_to_srgb(-0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_),
+        }
+    }
+}
+
+impl_from_via!(Rgb: Rgba, Hsv, Hsva, Hsl, Hsla, Xyz => Oklab);
+impl_from_via!(Rgb: Oklab => Rgba, Hsv, Hsva, Hsl, Hsla, Xyz);
+
+//
+// Implement to/from CssColorNotation
+//
+
+impl TryFrom<&css::CssColorNotation> for Oklab {
+    type Error = css::Error;
+
+    fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
+        match other.format {
+            css::CssColorType::Oklab => Ok(Self {
+                l: css::css_number_to_scaled(
+                    other.values.get(0).ok_or(css::Error::InvalidCssParams)?,
+                    1.0,
+                ),
+                a: css::css_number_to_scaled(
+                    other.values.get(1).ok_or(css::Error::InvalidCssParams)?,
+                    0.4,
+                ),
+                b: css::css_number_to_scaled(
+                    other.values.get(2).ok_or(css::Error::InvalidCssParams)?,
+                    0.4,
+                ),
+            }),
+            _ => Err(css::Error::WrongCssFormat),
+        }
+    }
+}
+
+impl From<Oklab> for css::CssColorNotation {
+    fn from(other: Oklab) -> Self {
+        Self::oklab(other.l, other.a, other.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case([0.0, 0.0, 0.0] ; "black")]
+    #[test_case([1.0, 1.0, 1.0] ; "white")]
+    #[test_case([1.0, 0.0, 0.0] ; "red")]
+    #[test_case([0.2, 0.4, 0.6] ; "steel blue")]
+    #[test_case([0.9, 0.75, 0.01] ; "amber")]
+    fn test_rgb_oklab_round_trip(rgb: [f64; 3]) {
+        let round_trip: [f64; 3] = Rgb::from(Oklab::from(Rgb::from(rgb))).into();
+
+        for (expected, actual) in rgb.iter().zip(round_trip) {
+            assert!(
+                (expected - actual).abs() < 1e-6,
+                "{rgb:?} != {round_trip:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_white() {
+        let Oklab { l, a, b } = Oklab::from(Rgb::from([1.0, 1.0, 1.0]));
+
+        assert!((l - 1.0).abs() < 1e-6, "l was {l}");
+        assert!(a.abs() < 1e-6 && b.abs() < 1e-6, "a was {a}, b was {b}");
+    }
+}
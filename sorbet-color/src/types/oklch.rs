@@ -0,0 +1,220 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{css, types::*, Color};
+
+/// Colors with a chroma below this are considered achromatic by [`mix_oklch`],
+/// so their hue is ignored.
+const ACHROMATIC_CHROMA: f64 = 1e-4;
+
+/// This structure represents colors in the polar form of [`Oklab`],
+/// with lightness, chroma, and hue channels.
+/// See the [reference post](<https://bottosson.github.io/posts/oklab/>) for details.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Oklch {
+    /// Perceptual lightness channel.
+    /// Ranged `0.0..1.0`.
+    pub l: f64,
+    /// Chroma channel, the distance from the neutral axis.
+    /// Ranged roughly `0.0..0.4`.
+    pub c: f64,
+    /// Hue channel.
+    /// Ranged `0.0..360.0`.
+    pub h: f64,
+}
+
+impl Eq for Oklch {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for Oklch {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.l.to_bits().hash(state);
+        self.c.to_bits().hash(state);
+        self.h.to_bits().hash(state);
+    }
+}
+
+impl Color for Oklch {
+    fn hex(&self) -> String {
+        Rgb::from(*self).hex()
+    }
+}
+
+/// Interpolates between `a` and `b` in [`Oklch`], where `t` of `0.0` is `a` and `1.0` is `b`.
+/// The `t` is clamped to `0.0..=1.0`.
+///
+/// Lightness and chroma are interpolated linearly, and hue takes the shortest path
+/// around the hue circle. If one of the colors is achromatic (its chroma is near zero),
+/// its hue is meaningless, so the hue of the other color is used for the whole mix.
+pub fn mix_oklch(a: Oklch, b: Oklch, t: f64) -> Oklch {
+    let t = t.clamp(0.0, 1.0);
+
+    let h = match (a.c < ACHROMATIC_CHROMA, b.c < ACHROMATIC_CHROMA) {
+        (true, false) => b.h,
+        (false, true) | (true, true) => a.h,
+        (false, false) => {
+            let delta = (b.h - a.h + 180.0).rem_euclid(360.0) - 180.0;
+
+            (a.h + delta * t).rem_euclid(360.0)
+        }
+    };
+
+    Oklch {
+        l: a.l + (b.l - a.l) * t,
+        c: a.c + (b.c - a.c) * t,
+        h,
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for Oklch {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            l: array[0],
+            c: array[1],
+            h: array[2],
+        }
+    }
+}
+
+impl From<Oklch> for [f64; 3] {
+    fn from(color: Oklch) -> Self {
+        [color.l, color.c, color.h]
+    }
+}
+
+//
+// Implement to/from Oklab
+//
+
+impl From<Oklab> for Oklch {
+    fn from(other: Oklab) -> Self {
+        Self {
+            l: other.l,
+            c: other.a.hypot(other.b),
+            h: other.b.atan2(other.a).to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(other: Oklch) -> Self {
+        let (sin, cos) = other.h.to_radians().sin_cos();
+
+        Self {
+            l: other.l,
+            a: other.c * cos,
+            b: other.c * sin,
+        }
+    }
+}
+
+impl_from_via!(Oklab: Rgb, Rgba => Oklch);
+impl_from_via!(Oklab: Oklch => Rgb, Rgba);
+impl_from_via!(Rgb: Hsv, Hsva, Hsl, Hsla, Xyz, Cmyk => Oklch);
+impl_from_via!(Rgb: Oklch => Hsv, Hsva, Hsl, Hsla, Xyz, Cmyk);
+
+//
+// Implement to/from CssColorNotation
+//
+
+impl TryFrom<&css::CssColorNotation> for Oklch {
+    type Error = css::Error;
+
+    fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
+        match other.format {
+            css::CssColorType::Oklch => Ok(Self {
+                l: css::css_number_to_scaled(
+                    other.values.get(0).ok_or(css::Error::InvalidCssParams)?,
+                    1.0,
+                ),
+                c: css::css_number_to_scaled(
+                    other.values.get(1).ok_or(css::Error::InvalidCssParams)?,
+                    0.4,
+                ),
+                h: other
+                    .values
+                    .get(2)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Hue),
+            }),
+            _ => Err(css::Error::WrongCssFormat),
+        }
+    }
+}
+
+impl From<Oklch> for css::CssColorNotation {
+    fn from(other: Oklch) -> Self {
+        Self::oklch(other.l, other.c, other.h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_from_hsl() {
+        let Oklch { l, c, h } = Oklch::from(Hsl::from([0.0, 1.0, 0.5]));
+
+        assert!((l - 0.628).abs() < 1e-3, "l was {l}");
+        assert!((c - 0.2577).abs() < 1e-3, "c was {c}");
+        assert!((h - 29.23).abs() < 1e-2, "h was {h}");
+        assert_eq!(Hsl::from(Oklch::from([l, c, h])).rgb_array(), [255, 0, 0]);
+    }
+
+    #[test]
+    fn test_oklab_round_trip() {
+        let oklab = Oklab::from([0.6, -0.1, 0.12]);
+        let round_trip: [f64; 3] = Oklab::from(Oklch::from(oklab)).into();
+
+        for (expected, actual) in [0.6, -0.1, 0.12].iter().zip(round_trip) {
+            assert!((expected - actual).abs() < 1e-12, "{round_trip:?}");
+        }
+    }
+
+    #[test_case(350.0, 30.0 => 10.0 ; "across zero")]
+    #[test_case(30.0, 350.0 => 10.0 ; "across zero backward")]
+    #[test_case(300.0, 200.0 => 250.0 ; "backward")]
+    #[test_case(100.0, 200.0 => 150.0 ; "forward")]
+    fn test_mix_oklch_shortest_hue(a: f64, b: f64) -> f64 {
+        let mixed = mix_oklch(Oklch::from([0.5, 0.1, a]), Oklch::from([0.5, 0.1, b]), 0.5);
+
+        (mixed.h * 1e9).round() / 1e9
+    }
+
+    #[test]
+    fn test_mix_oklch_achromatic_takes_other_hue() {
+        let gray = Oklch::from([0.5, 0.0, 0.0]);
+        let blue = Oklch::from([0.5, 0.2, 264.0]);
+
+        assert_eq!(mix_oklch(gray, blue, 0.25).h, 264.0);
+        assert_eq!(mix_oklch(blue, gray, 0.75).h, 264.0);
+    }
+
+    #[test]
+    fn test_mix_oklch_clamps_t() {
+        let a = Oklch::from([0.2, 0.1, 40.0]);
+        let b = Oklch::from([0.8, 0.2, 80.0]);
+
+        assert_eq!(mix_oklch(a, b, -1.0), a);
+        assert_eq!(mix_oklch(a, b, 2.0), b);
+    }
+}
@@ -0,0 +1,184 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::types::*;
+
+/// This structure represents colors in the RGBA color space with the red, green, and blue
+/// channels already multiplied by the alpha channel, as used by most renderers and image formats
+/// for compositing.
+///
+/// Blending is simpler in this form; see [`PremulRgba::over`]. The rest of this crate expects
+/// straight (non-premultiplied) alpha, so convert to [`Rgba`] with [`From`] before using it with
+/// anything else. See also [`Rgba::premultiply`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PremulRgba {
+    /// Red channel, multiplied by alpha.
+    /// Ranged `0.0..alpha`.
+    pub r: f64,
+    /// Green channel, multiplied by alpha.
+    /// Ranged `0.0..alpha`.
+    pub g: f64,
+    /// Blue channel, multiplied by alpha.
+    /// Ranged `0.0..alpha`.
+    pub b: f64,
+    /// Alpha/transparency channel.
+    /// Ranged `0.0..1.0`.
+    pub alpha: f64,
+}
+
+impl Eq for PremulRgba {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for PremulRgba {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.r.to_bits().hash(state);
+        self.g.to_bits().hash(state);
+        self.b.to_bits().hash(state);
+        self.alpha.to_bits().hash(state);
+    }
+}
+
+impl PremulRgba {
+    /// Composites this color over `background` with the Porter-Duff source-over operator.
+    /// This gives the same result as [`Rgba::over`], without dividing by alpha.
+    #[must_use]
+    pub fn over(self, background: PremulRgba) -> PremulRgba {
+        let remaining = 1.0 - self.alpha;
+
+        Self {
+            r: self.r + background.r * remaining,
+            g: self.g + background.g * remaining,
+            b: self.b + background.b * remaining,
+            alpha: self.alpha + background.alpha * remaining,
+        }
+    }
+
+    /// Returns the channels as straight-alpha 8-bit sRGB, as written to a PNG file,
+    /// dividing out the alpha before rounding so that dark translucent colors keep their precision.
+    /// See [`Rgba::to_straight_srgb8`].
+    pub fn to_straight_srgb8(&self) -> [u8; 4] {
+        Rgba::from(*self).to_straight_srgb8()
+    }
+
+    /// Adds the channels of `other` to this color, clamping each to `1.0`.
+    /// This is the Porter-Duff plus operator, also known as additive blending.
+    #[must_use]
+    pub fn plus(self, other: PremulRgba) -> PremulRgba {
+        Self {
+            r: (self.r + other.r).min(1.0),
+            g: (self.g + other.g).min(1.0),
+            b: (self.b + other.b).min(1.0),
+            alpha: (self.alpha + other.alpha).min(1.0),
+        }
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 4]> for PremulRgba {
+    fn from(array: [f64; 4]) -> Self {
+        Self {
+            r: array[0],
+            g: array[1],
+            b: array[2],
+            alpha: array[3],
+        }
+    }
+}
+
+impl From<PremulRgba> for [f64; 4] {
+    fn from(color: PremulRgba) -> Self {
+        [color.r, color.g, color.b, color.alpha]
+    }
+}
+
+//
+// Implement to/from Rgba
+//
+
+impl From<Rgba> for PremulRgba {
+    fn from(other: Rgba) -> Self {
+        Self {
+            r: other.r * other.alpha,
+            g: other.g * other.alpha,
+            b: other.b * other.alpha,
+            alpha: other.alpha,
+        }
+    }
+}
+
+/// If the alpha channel is `0.0`, the color channels are unrecoverable
+/// and the result is transparent black.
+impl From<PremulRgba> for Rgba {
+    fn from(other: PremulRgba) -> Self {
+        if other.alpha == 0.0 {
+            return Self::from([0.0, 0.0, 0.0, 0.0]);
+        }
+
+        Self {
+            r: other.r / other.alpha,
+            g: other.g / other.alpha,
+            b: other.b / other.alpha,
+            alpha: other.alpha,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case([0.2, 0.4, 0.6, 0.5] ; "half")]
+    #[test_case([1.0, 0.5, 0.0, 0.25] ; "quarter")]
+    #[test_case([0.3, 0.3, 0.9, 1.0] ; "opaque")]
+    fn test_round_trip(channels: [f64; 4]) {
+        let rgba = Rgba::from(channels);
+        let round_trip = Rgba::from(PremulRgba::from(rgba));
+
+        for (expected, actual) in channels.iter().zip(<[f64; 4]>::from(round_trip)) {
+            assert!(
+                (expected - actual).abs() < 1e-12,
+                "{rgba:?} != {round_trip:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_straight_srgb8() {
+        let premultiplied = PremulRgba::from([0.5, 0.25, 0.1, 0.5]);
+
+        assert_eq!(premultiplied.to_straight_srgb8(), [255, 128, 51, 128]);
+    }
+
+    #[test]
+    fn test_transparent() {
+        let transparent = PremulRgba::from([0.0, 0.0, 0.0, 0.0]);
+
+        assert_eq!(Rgba::from(transparent), Rgba::from([0.0, 0.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_over_matches_straight() {
+        let top = Rgba::from([1.0, 0.0, 0.0, 0.5]);
+        let bottom = Rgba::from([0.0, 0.0, 1.0, 0.5]);
+        let premultiplied = Rgba::from(PremulRgba::from(top).over(bottom.into()));
+
+        assert_eq!(premultiplied, top.over(bottom));
+    }
+}
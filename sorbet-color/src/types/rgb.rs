@@ -14,7 +14,13 @@
  * limitations under the License.
  */
 
-use crate::{css, types::*, Color};
+use crate::{
+    adaptation::{chromatic_adapt_xyz, WhitePoint},
+    css,
+    gamut::{linear_to_srgb, srgb_to_linear},
+    types::*,
+    Color,
+};
 
 /// This structure represents colors in the RGB color space with
 /// red, green, and blue channels.
@@ -37,7 +43,29 @@ pub struct Rgb {
 
 impl Color for Rgb {
     fn hex(&self) -> String {
-        format!("#{:06X}", u32::from(*self) >> 8)
+        format!("{:#X}", self)
+    }
+}
+
+/// Formats the color as 6 lowercase hexadecimal digits.
+/// With the alternate flag (`{:#x}`) the digits are prefixed with a `#` rather than `0x`.
+impl std::fmt::LowerHex for Rgb {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if formatter.alternate() {
+            formatter.write_str("#")?;
+        }
+        formatter.write_fmt(format_args!("{:06x}", u32::from(*self) >> 8))
+    }
+}
+
+/// Formats the color as 6 uppercase hexadecimal digits.
+/// With the alternate flag (`{:#X}`) the digits are prefixed with a `#` rather than `0x`.
+impl std::fmt::UpperHex for Rgb {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if formatter.alternate() {
+            formatter.write_str("#")?;
+        }
+        formatter.write_fmt(format_args!("{:06X}", u32::from(*self) >> 8))
     }
 }
 
@@ -52,6 +80,306 @@ impl std::hash::Hash for Rgb {
     }
 }
 
+impl Rgb {
+    /// Creates a color from the red, green, and blue channels.
+    ///
+    /// Unlike [`From`], this is a `const fn`, so it can be used to define constants,
+    /// such as `const BACKGROUND: Rgb = Rgb::from_channels(0.1, 0.1, 0.1);`.
+    /// The channels are not checked or clamped.
+    pub const fn from_channels(r: f64, g: f64, b: f64) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Returns the color with every channel clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        Self {
+            r: self.r.clamp(0.0, 1.0),
+            g: self.g.clamp(0.0, 1.0),
+            b: self.b.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns the color with `function` applied to each channel, such as to quantize it.
+    /// See [`Rgba::map_channels`].
+    #[must_use]
+    pub fn map_channels(self, function: impl Fn(f64) -> f64) -> Self {
+        Self {
+            r: function(self.r),
+            g: function(self.g),
+            b: function(self.b),
+        }
+    }
+
+    /// Decodes the channels from the sRGB transfer function ("gamma") into linear light,
+    /// where they are proportional to the light emitted, as needed for physically correct blending.
+    /// Negative channels are decoded by symmetry, so that colors out of gamut survive a round trip.
+    ///
+    /// The result is not an sRGB color, so most other methods of [`Rgb`] give wrong results for it.
+    /// See [`Rgb::to_srgb`] for the inverse.
+    #[must_use]
+    pub fn to_linear(self) -> Rgb {
+        self.map_channels(srgb_to_linear)
+    }
+
+    /// Encodes channels in linear light with the sRGB transfer function, the inverse of [`Rgb::to_linear`].
+    #[must_use]
+    pub fn to_srgb(self) -> Rgb {
+        self.map_channels(linear_to_srgb)
+    }
+
+    /// Returns the color as [`Rgba`] with the alpha channel `alpha`, such as `color.with_alpha(0.5)`.
+    #[must_use]
+    pub fn with_alpha(self, alpha: f64) -> Rgba {
+        Rgba {
+            r: self.r,
+            g: self.g,
+            b: self.b,
+            alpha,
+        }
+    }
+
+    /// Creates a color from 8-bit channels as a `const fn`, the same as `Rgb::from([r, g, b])`.
+    ///
+    /// Each channel is divided by `255.0` when the constant is evaluated, which relies on the
+    /// floating-point arithmetic in `const fn` that became stable in Rust 1.82.
+    /// The channels are stored as floats, as for every other constructor.
+    pub const fn from_u8_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            r: r as f64 / 255.0,
+            g: g as f64 / 255.0,
+            b: b as f64 / 255.0,
+        }
+    }
+
+    /// Composites `top` over `bottom` with the Porter-Duff source-over operator,
+    /// returning the resulting color and alpha as separate values rather than as an [`Rgba`].
+    ///
+    /// Both inputs and the returned color use straight (non-premultiplied) alpha.
+    /// If both inputs are fully transparent, the result is black with an alpha of `0.0`.
+    pub fn composite_parts(top: Rgba, bottom: Rgba) -> (Rgb, f64) {
+        let alpha = top.alpha + bottom.alpha * (1.0 - top.alpha);
+
+        if alpha == 0.0 {
+            return (Rgb::from([0.0, 0.0, 0.0]), 0.0);
+        }
+
+        let blend = |t: f64, b: f64| (t * top.alpha + b * bottom.alpha * (1.0 - top.alpha)) / alpha;
+
+        (
+            Rgb {
+                r: blend(top.r, bottom.r),
+                g: blend(top.g, bottom.g),
+                b: blend(top.b, bottom.b),
+            },
+            alpha,
+        )
+    }
+
+    /// Converts the color to [`Hsl`] and applies [`Hsl::lighten`].
+    #[must_use]
+    pub fn lighten(self, amount: f64) -> Self {
+        Hsl::from(self).lighten(amount).into()
+    }
+
+    /// Converts the color to [`Hsl`] and applies [`Hsl::darken`].
+    #[must_use]
+    pub fn darken(self, amount: f64) -> Self {
+        Hsl::from(self).darken(amount).into()
+    }
+
+    /// Converts the color to [`Hsl`] and applies [`Hsl::saturate`].
+    #[must_use]
+    pub fn saturate(self, amount: f64) -> Self {
+        Hsl::from(self).saturate(amount).into()
+    }
+
+    /// Converts the color to [`Hsl`] and applies [`Hsl::desaturate`].
+    #[must_use]
+    pub fn desaturate(self, amount: f64) -> Self {
+        Hsl::from(self).desaturate(amount).into()
+    }
+
+    /// Returns the relative luminance of the color as defined by
+    /// [WCAG 2.1](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance),
+    /// ranged `0.0..=1.0` from black to white. Channels are clamped into `0.0..=1.0` first.
+    pub fn relative_luminance(&self) -> f64 {
+        let [r, g, b] =
+            [self.r, self.g, self.b].map(|channel| srgb_to_linear(channel.clamp(0.0, 1.0)));
+
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    /// Returns the gray with the same [relative luminance](Rgb::relative_luminance) as this color,
+    /// which uses the Rec. 709 weights in linear light. Unlike [`Rgb::desaturate`], this preserves
+    /// the perceived brightness, so a saturated blue becomes a dark gray rather than a middle gray.
+    pub fn grayscale(&self) -> Rgb {
+        let gray = linear_to_srgb(self.relative_luminance());
+
+        Self {
+            r: gray,
+            g: gray,
+            b: gray,
+        }
+    }
+
+    /// Returns `true` if the red, green, and blue channels are all within `epsilon` of each other.
+    pub fn is_grayscale(&self, epsilon: f64) -> bool {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+
+        max - min <= epsilon
+    }
+
+    /// Returns the perceptual difference between this color and `other`, using CIEDE2000.
+    /// Both colors are converted to [`Lab`] first. See [`crate::distance::delta_e_2000`].
+    pub fn difference(&self, other: Rgb) -> f64 {
+        crate::distance::delta_e_2000(Lab::from(*self), Lab::from(other))
+    }
+
+    /// Returns how this color would appear on a display with the given `gamma` and `white` point,
+    /// as seen on a standard sRGB display. This is useful for checking how a theme looks on
+    /// a warmer or cooler screen.
+    ///
+    /// The channels are decoded with a pure power curve of `gamma` rather than the sRGB transfer
+    /// function, and the color is adapted from D65 to `white` with
+    /// [`crate::adaptation::chromatic_adapt_xyz`]. The `white` point should have a `y` of `1.0`,
+    /// such as from [`crate::adaptation::WhitePoint::xyz`].
+    ///
+    /// This is a simplified soft-proof, and not a substitute for a full ICC color management
+    /// pipeline with a measured display profile.
+    #[must_use]
+    pub fn simulate_display(self, gamma: f64, white: Xyz) -> Rgb {
+        let linear = [self.r, self.g, self.b].map(|channel| channel.clamp(0.0, 1.0).powf(gamma));
+        // Re-encode so that the conversion to XYZ decodes the display's own curve.
+        let xyz = Xyz::from(Rgb::from(linear.map(linear_to_srgb)));
+
+        Rgb::from(chromatic_adapt_xyz(xyz, WhitePoint::D65.xyz(), white))
+    }
+
+    /// Tints the dark parts of an image toward `shadow` and the bright parts toward `highlight`,
+    /// the color grading effect known as split toning. Call this for each pixel.
+    ///
+    /// The pivot between the two is at a relative luminance of `0.5`, and `balance` shifts it,
+    /// ranged `-1.0..=1.0`, where a positive value lifts the pivot so that more of the image
+    /// is tinted toward `shadow`. The tint is strongest at black and white, which become
+    /// `shadow` and `highlight` respectively, and fades to nothing at the pivot.
+    ///
+    /// The blend is done in linear light. See [`Rgb::relative_luminance`].
+    #[must_use]
+    pub fn split_tone(self, shadow: Rgb, highlight: Rgb, balance: f64) -> Rgb {
+        let luminance = self.relative_luminance();
+        let pivot = (1.0 + balance.clamp(-1.0, 1.0)) / 2.0;
+        let (tint, amount) = match () {
+            _ if luminance < pivot => (shadow, 1.0 - luminance / pivot),
+            _ if luminance > pivot => (highlight, (luminance - pivot) / (1.0 - pivot)),
+            _ => return self,
+        };
+
+        let [r, g, b] = [(self.r, tint.r), (self.g, tint.g), (self.b, tint.b)].map(|(from, to)| {
+            let from = srgb_to_linear(from);
+
+            linear_to_srgb(from + (srgb_to_linear(to) - from) * amount)
+        });
+
+        Self { r, g, b }
+    }
+
+    /// Mixes this color toward white, where `amount` of `0.0` is this color and `1.0` is white.
+    /// This is the tint of color theory, see also [`Rgb::shade`] and [`Rgb::tone`].
+    ///
+    /// This is [`Color::mix`], so the mix is done in the gamma-encoded channels and `amount` is clamped
+    /// to `0.0..=1.0`. Other color types can be converted with `.into()` first.
+    #[must_use]
+    pub fn tint(self, amount: f64) -> Rgb {
+        self.mix(Rgb::from([1.0; 3]), amount)
+    }
+
+    /// Mixes this color toward black, where `amount` of `0.0` is this color and `1.0` is black.
+    /// See [`Rgb::tint`].
+    #[must_use]
+    pub fn shade(self, amount: f64) -> Rgb {
+        self.mix(Rgb::from([0.0; 3]), amount)
+    }
+
+    /// Mixes this color toward the middle gray `#808080`, where `amount` of `0.0` is this color
+    /// and `1.0` is the gray. See [`Rgb::tint`].
+    #[must_use]
+    pub fn tone(self, amount: f64) -> Rgb {
+        self.mix(Rgb::from([0.5; 3]), amount)
+    }
+
+    /// Mixes this color with `other` in [`Okhsl`], where `t` of `0.0` is this color and `1.0` is `other`.
+    /// This is like mixing in [`Hsl`], but the lightness changes evenly as perceived. See [`mix_okhsl`].
+    #[must_use]
+    pub fn mix_okhsl(self, other: Rgb, t: f64) -> Rgb {
+        mix_okhsl(self.into(), other.into(), t).into()
+    }
+
+    /// Mixes this color with `other` by interpolating their ink channels in [`Cmyk`],
+    /// where `t` of `0.0` is this color and `1.0` is `other`, and is clamped to `0.0..=1.0`.
+    ///
+    /// This is a rough approximation of subtractive mixing that is cheap to compute,
+    /// since the naive CMYK conversion knows nothing of real inks or pigments.
+    /// Mixing colors this way tends to give a darker result than [`Color::mix`], like ink does,
+    /// but for pigment-like hues such as blue and yellow giving green, see [`Ryb`].
+    #[must_use]
+    pub fn mix_cmyk(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let a: [f64; 4] = Cmyk::from(self).into();
+        let b: [f64; 4] = Cmyk::from(other).into();
+        let mut mixed = [0.0; 4];
+
+        for (channel, (a, b)) in mixed.iter_mut().zip(a.into_iter().zip(b)) {
+            *channel = a + (b - a) * t;
+        }
+
+        Cmyk::from(mixed).into()
+    }
+
+    /// Converts the color to the [`Hsi`] model, the same as [`Hsi::from`].
+    pub fn to_hsi(self) -> Hsi {
+        Hsi::from(self)
+    }
+
+    /// Converts the color to [`Yuv`] using the luma coefficients of `standard`.
+    /// See [`Yuv::from_rgb`].
+    pub fn to_yuv(self, standard: LumaStandard) -> Yuv {
+        Yuv::from_rgb(self, standard)
+    }
+
+    /// Converts a [`Yuv`] color to [`Rgb`] using the luma coefficients of `standard`.
+    /// See [`Yuv::to_rgb`].
+    pub fn from_yuv(yuv: Yuv, standard: LumaStandard) -> Self {
+        yuv.to_rgb(standard)
+    }
+
+    /// Returns the color as a hexadecimal string prefixed with a `#`, such as `#ff8000`.
+    ///
+    /// If `shorthand` is `true` and both digits of every channel are the same,
+    /// the three-digit form is returned instead, such as `#fff` rather than `#ffffff`.
+    pub fn to_hex(&self, uppercase: bool, shorthand: bool) -> String {
+        format_hex(&<[u8; 3]>::from(*self), uppercase, shorthand)
+    }
+}
+
+/// Formats each byte as two hexadecimal digits after a `#`, or as one digit per byte
+/// if `shorthand` is `true` and every byte is made of two identical digits.
+pub(super) fn format_hex(bytes: &[u8], uppercase: bool, shorthand: bool) -> String {
+    let shorthand = shorthand && bytes.iter().all(|byte| byte >> 4 == byte & 0xF);
+
+    bytes.iter().fold(String::from("#"), |mut hex, byte| {
+        let digits = match (shorthand, uppercase) {
+            (true, false) => format!("{:x}", byte & 0xF),
+            (true, true) => format!("{:X}", byte & 0xF),
+            (false, false) => format!("{:02x}", byte),
+            (false, true) => format!("{:02X}", byte),
+        };
+        hex.push_str(&digits);
+        hex
+    })
+}
+
 //
 // Implement to/from primitives
 //
@@ -110,24 +438,28 @@ impl From<Rgb> for [f64; 3] {
     }
 }
 
-impl From<&str> for Rgb {
-    /// Takes a hexadecimal string optionally prefixed with a `#` and returns an [`Rgb`] structure.
-    /// The input is unchecked and will panic if the input has invalid hexadecimal characters.
-    /// For a more robust constructor that won't panic, see [`crate::Color::new`].
-    fn from(string: &str) -> Self {
+impl TryFrom<&str> for Rgb {
+    type Error = css::Error;
+
+    /// Takes a six-digit hexadecimal string optionally prefixed with a `#` and returns an [`Rgb`] structure.
+    /// Returns [`css::Error::InvalidHexChars`] or [`css::Error::InvalidHexLength`] if the input is invalid.
+    fn try_from(string: &str) -> css::Result<Self> {
         let string = string.strip_prefix('#').unwrap_or(string);
 
-        const EXPECT_MSG: &str = "invalid hexadecimal string";
+        if !string.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(css::Error::InvalidHexChars);
+        } else if string.len() != 6 {
+            return Err(css::Error::InvalidHexLength);
+        }
 
-        let r = u8::from_str_radix(&string[0..2], 16).expect(EXPECT_MSG);
-        let g = u8::from_str_radix(&string[2..4], 16).expect(EXPECT_MSG);
-        let b = u8::from_str_radix(&string[4..6], 16).expect(EXPECT_MSG);
+        // The characters were checked above, so these can not fail.
+        let channel = |range| u8::from_str_radix(&string[range], 16).unwrap() as f64 / 255.0;
 
-        Self {
-            r: r as f64 / 255.0,
-            g: g as f64 / 255.0,
-            b: b as f64 / 255.0,
-        }
+        Ok(Self {
+            r: channel(0..2),
+            g: channel(2..4),
+            b: channel(4..6),
+        })
     }
 }
 
@@ -139,7 +471,7 @@ impl From<Hsv> for Rgb {
     fn from(other: Hsv) -> Self {
         //https://en.wikipedia.org/wiki/HSL_and_HSV#HSV_to_RGB
         let c = other.v * other.s;
-        let h1 = other.h / 60.0;
+        let h1 = other.h.rem_euclid(360.0) / 60.0;
         let x = c * (1.0 - (h1 % 2.0 - 1.0).abs());
         let (r1, g1, b1) = neighboring(c, x, h1);
         let m = other.v - c;
@@ -153,7 +485,7 @@ impl From<Hsl> for Rgb {
     fn from(other: Hsl) -> Self {
         // https://en.wikipedia.org/wiki/HSL_and_HSV#HSL_to_RGB
         let c = (1.0 - (2.0 * other.l - 1.0).abs()) * other.s;
-        let h1 = other.h / 60.0;
+        let h1 = other.h.rem_euclid(360.0) / 60.0;
         let x = c * (1.0 - (h1 % 2.0 - 1.0).abs());
         let (r1, g1, b1) = neighboring(c, x, h1);
         let m = other.l - (c / 2.0);
@@ -195,15 +527,21 @@ impl TryFrom<&css::CssColorNotation> for Rgb {
     fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
         match other.format {
             css::CssColorType::Rgb | css::CssColorType::Rgba => Ok(Self {
-                r: css::css_number_to_rgb_channel(
-                    other.values.get(0).ok_or(css::Error::InvalidCssParams)?,
-                ),
-                g: css::css_number_to_rgb_channel(
-                    other.values.get(1).ok_or(css::Error::InvalidCssParams)?,
-                ),
-                b: css::css_number_to_rgb_channel(
-                    other.values.get(2).ok_or(css::Error::InvalidCssParams)?,
-                ),
+                r: other
+                    .values
+                    .get(0)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Rgb),
+                g: other
+                    .values
+                    .get(1)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Rgb),
+                b: other
+                    .values
+                    .get(2)
+                    .ok_or(css::Error::InvalidCssParams)?
+                    .clamp_channel(css::ChannelKind::Rgb),
             }),
             _ => Err(css::Error::WrongCssFormat),
         }
@@ -212,14 +550,7 @@ impl TryFrom<&css::CssColorNotation> for Rgb {
 
 impl From<Rgb> for css::CssColorNotation {
     fn from(other: Rgb) -> Self {
-        Self {
-            format: css::CssColorType::Rgb,
-            values: vec![
-                css::CssNumber::Float(other.r * 255.0),
-                css::CssNumber::Float(other.g * 255.0),
-                css::CssNumber::Float(other.b * 255.0),
-            ],
-        }
+        Self::rgb(other.r, other.g, other.b)
     }
 }
 
@@ -261,3 +592,215 @@ fn neighboring(c: f64, x: f64, h1: f64) -> (f64, f64, f64) {
         _ => (0.0, 0.0, 0.0),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Rgb::from([0.3, 0.6, 0.9]) ; "blue")]
+    #[test_case(Rgb::from_hex("#C0392B").unwrap() ; "red")]
+    #[test_case(Hsl::from([75.0, 0.4, 0.7]).into() ; "from hsl")]
+    fn test_tint_shade_tone(color: Rgb) {
+        assert_eq!(color.tint(1.0), Rgb::from([1.0; 3]));
+        assert_eq!(color.shade(1.0), Rgb::from([0.0; 3]));
+        assert_eq!(color.tone(1.0), Rgb::from([0.5; 3]));
+        assert_eq!(color.tint(0.0), color);
+        assert_eq!(color.shade(-0.5), color);
+        assert_eq!(Rgb::from([0.0; 3]).tint(0.25), Rgb::from([0.25; 3]));
+    }
+
+    #[test]
+    fn test_linear_round_trip() {
+        for i in 0..=1000 {
+            let channel = f64::from(i) / 1000.0;
+            let color = Rgb::from([channel, 1.0 - channel, channel / 2.0]);
+            let round_trip = color.to_linear().to_srgb();
+
+            for (expected, actual) in <[f64; 3]>::from(color).iter().zip(round_trip) {
+                assert!(
+                    (expected - actual).abs() < 1e-9,
+                    "{color:?} != {round_trip:?}"
+                );
+            }
+        }
+    }
+
+    #[test_case(0.5 => 0.214 ; "middle gray")]
+    #[test_case(0.04 => 0.003 ; "linear segment")]
+    #[test_case(1.0 => 1.0 ; "white")]
+    fn test_to_linear(channel: f64) -> f64 {
+        (Rgb::from([channel; 3]).to_linear().g * 1e3).round() / 1e3
+    }
+
+    #[test]
+    fn test_grayscale() {
+        let blue = Rgb::from([0.0, 0.0, 1.0]);
+        let gray = blue.grayscale();
+
+        assert!(gray.is_grayscale(0.0));
+        assert!(!blue.is_grayscale(0.5));
+        assert!((gray.relative_luminance() - blue.relative_luminance()).abs() < 1e-9);
+        assert_eq!(
+            Rgb::from([0.5, 0.5, 0.5]).grayscale().rgb_array(),
+            [128, 128, 128]
+        );
+        assert!(Rgb::from([0.5, 0.501, 0.499]).is_grayscale(0.005));
+    }
+
+    #[test]
+    fn test_split_tone() {
+        let shadow = Rgb::from([0.0, 0.2, 0.4]);
+        let highlight = Rgb::from([1.0, 0.8, 0.4]);
+        let black = Rgb::from([0.0, 0.0, 0.0]);
+        let white = Rgb::from([1.0, 1.0, 1.0]);
+
+        assert!(black.split_tone(shadow, highlight, 0.0).difference(shadow) < 1e-6);
+        assert!(
+            white
+                .split_tone(shadow, highlight, 0.0)
+                .difference(highlight)
+                < 1e-6
+        );
+
+        let dark = Rgb::from([0.2, 0.2, 0.2]);
+        let toned = dark.split_tone(shadow, highlight, 0.0);
+
+        assert!(toned.difference(shadow) < dark.difference(shadow));
+        assert!(toned.b > toned.r);
+    }
+
+    #[test]
+    fn test_const_constructors() {
+        const BACKGROUND: Rgb = Rgb::from_channels(0.1, 0.2, 0.3);
+        const ACCENT: Rgb = Rgb::from_u8_rgb(0x35, 0x80, 0xE4);
+
+        assert_eq!(BACKGROUND, Rgb::from([0.1, 0.2, 0.3]));
+        assert_eq!(ACCENT, Rgb::from([0x35, 0x80, 0xE4]));
+        assert_eq!(
+            Hsla::from_channels(210.0, 0.5, 0.4, 0.8),
+            Hsla::from([210.0, 0.5, 0.4, 0.8])
+        );
+    }
+
+    #[test_case([255, 255, 255], false, true => "#fff" ; "collapsible")]
+    #[test_case([255, 255, 255], true, false => "#FFFFFF" ; "shorthand disabled")]
+    #[test_case([0xAA, 0x33, 0x00], true, true => "#A30" ; "collapsible uppercase")]
+    #[test_case([0xAA, 0x34, 0x00], false, true => "#aa3400" ; "not collapsible")]
+    fn test_to_hex(array: [u8; 3], uppercase: bool, shorthand: bool) -> String {
+        Rgb::from(array).to_hex(uppercase, shorthand)
+    }
+
+    #[test_case([0.0, 0.0, 0.0] ; "black")]
+    #[test_case([1.0, 1.0, 1.0] ; "white")]
+    #[test_case([0.2, 0.4, 0.6] ; "steel blue")]
+    #[test_case([0.9, 0.1, 0.5] ; "pink")]
+    fn test_simulate_display_identity(rgb: [f64; 3]) {
+        let color = Rgb::from(rgb);
+        let simulated = color.simulate_display(2.2, WhitePoint::D65.xyz());
+
+        // A 2.2 power curve is close to, but not exactly, the sRGB transfer function
+        assert!(
+            color.difference(simulated) < crate::distance::JUST_NOTICEABLE_DIFFERENCE,
+            "{color:?} != {simulated:?}"
+        );
+    }
+
+    #[test]
+    fn test_simulate_display_warm() {
+        let white = Rgb::from([1.0, 1.0, 1.0]).simulate_display(2.2, WhitePoint::D50.xyz());
+
+        assert!(white.b < white.r, "{white:?} is not warmer");
+    }
+
+    #[test]
+    fn test_lighten_darken() {
+        let color = Rgb::from([0.2, 0.4, 0.6]);
+
+        assert_eq!(
+            Rgb::from([0.0, 0.0, 0.0]).lighten(1.0).rgb_array(),
+            [255; 3]
+        );
+        assert_eq!(
+            color.lighten(0.1).darken(0.1).rgb_array(),
+            color.rgb_array()
+        );
+        assert_eq!(color.desaturate(1.0).rgb_array(), [102; 3]);
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        assert_eq!(
+            Rgb::try_from("#336699").unwrap(),
+            Rgb::from([51_u8, 102, 153])
+        );
+        assert_eq!(
+            Rgb::try_from("336699").unwrap(),
+            Rgb::from([51_u8, 102, 153])
+        );
+        assert!(matches!(
+            Rgb::try_from("zzз"),
+            Err(css::Error::InvalidHexChars)
+        ));
+        assert!(matches!(
+            Rgb::try_from("#3369"),
+            Err(css::Error::InvalidHexLength)
+        ));
+    }
+
+    #[test]
+    fn test_hex_formatting() {
+        let color = Rgb::from([255, 128, 0]);
+
+        assert_eq!(format!("{:x}", color), "ff8000");
+        assert_eq!(format!("{:X}", color), "FF8000");
+        assert_eq!(format!("{:#x}", color), "#ff8000");
+        assert_eq!(format!("{:#X}", color), "#FF8000");
+        assert_eq!(format!("{:x}", Rgb::from([0, 0, 1])), "000001");
+    }
+
+    #[test]
+    fn test_composite_parts_over_opaque() {
+        let top = Rgba::from([1.0, 0.0, 0.0, 0.5]);
+        let bottom = Rgba::from([1.0, 1.0, 1.0, 1.0]);
+
+        assert_eq!(
+            Rgb::composite_parts(top, bottom),
+            (Rgb::from([1.0, 0.5, 0.5]), 1.0)
+        );
+    }
+
+    #[test]
+    fn test_composite_parts_over_translucent() {
+        let top = Rgba::from([1.0, 0.0, 0.0, 0.5]);
+        let bottom = Rgba::from([0.0, 0.0, 1.0, 0.5]);
+        let (color, alpha) = Rgb::composite_parts(top, bottom);
+
+        assert_eq!(alpha, 0.75);
+        assert!((color.r - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(color.g, 0.0);
+        assert!((color.b - 1.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_composite_parts_transparent() {
+        let clear = Rgba::from([0.5, 0.5, 0.5, 0.0]);
+
+        assert_eq!(
+            Rgb::composite_parts(clear, clear),
+            (Rgb::from([0.0, 0.0, 0.0]), 0.0)
+        );
+    }
+
+    #[test]
+    fn test_mix_cmyk() {
+        let blue = Rgb::from_hex("#1E3C96").unwrap();
+        let yellow = Rgb::from_hex("#F0D21E").unwrap();
+        let mixed = blue.mix_cmyk(yellow, 0.5);
+
+        assert!(mixed.difference(blue.mix(yellow, 0.5)) > 5.0, "{mixed:?}");
+        assert!(mixed.relative_luminance() < blue.mix(yellow, 0.5).relative_luminance());
+        assert_eq!(blue.mix_cmyk(yellow, 0.0).hex(), blue.hex());
+        assert_eq!(blue.mix_cmyk(yellow, 2.0).hex(), yellow.hex());
+    }
+}
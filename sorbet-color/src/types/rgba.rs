@@ -14,7 +14,12 @@
  * limitations under the License.
  */
 
-use crate::{css, types::*, Color};
+use crate::{
+    css,
+    gamut::{linear_to_srgb, srgb_to_linear},
+    types::*,
+    Color,
+};
 
 /// This structure represents colors in the RGBA color space with
 /// red, green, blue, and alpha channels..
@@ -39,7 +44,29 @@ pub struct Rgba {
 
 impl Color for Rgba {
     fn hex(&self) -> String {
-        format!("#{:08X}", u32::from(*self))
+        format!("{:#X}", self)
+    }
+}
+
+/// Formats the color as 8 lowercase hexadecimal digits.
+/// With the alternate flag (`{:#x}`) the digits are prefixed with a `#` rather than `0x`.
+impl std::fmt::LowerHex for Rgba {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if formatter.alternate() {
+            formatter.write_str("#")?;
+        }
+        formatter.write_fmt(format_args!("{:08x}", u32::from(*self)))
+    }
+}
+
+/// Formats the color as 8 uppercase hexadecimal digits.
+/// With the alternate flag (`{:#X}`) the digits are prefixed with a `#` rather than `0x`.
+impl std::fmt::UpperHex for Rgba {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if formatter.alternate() {
+            formatter.write_str("#")?;
+        }
+        formatter.write_fmt(format_args!("{:08X}", u32::from(*self)))
     }
 }
 
@@ -55,6 +82,148 @@ impl std::hash::Hash for Rgba {
     }
 }
 
+impl Rgba {
+    /// Creates a color from the red, green, blue, and alpha channels as a `const fn`.
+    /// See [`Rgb::from_channels`].
+    pub const fn from_channels(r: f64, g: f64, b: f64, alpha: f64) -> Self {
+        Self { r, g, b, alpha }
+    }
+
+    /// Returns the color with every channel clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn clamped(self) -> Self {
+        Self {
+            r: self.r.clamp(0.0, 1.0),
+            g: self.g.clamp(0.0, 1.0),
+            b: self.b.clamp(0.0, 1.0),
+            alpha: self.alpha.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Returns the color with the alpha channel replaced by `alpha`, such as `color.with_alpha(0.5)`.
+    #[must_use]
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Self { alpha, ..self }
+    }
+
+    /// Returns the color as a hexadecimal string prefixed with a `#`,
+    /// omitting the alpha digits if the color is opaque, such as `#ff0000` rather than `#ff0000ff`.
+    /// The color counts as opaque if its alpha rounds to `255` as an 8-bit integer.
+    ///
+    /// If `shorthand` is `true` and both digits of every channel are the same,
+    /// the one-digit-per-channel form is returned instead, such as `#f008` rather than `#ff000088`.
+    /// For the full eight digits regardless of alpha, use [`Color::hex`].
+    pub fn to_hex(&self, uppercase: bool, shorthand: bool) -> String {
+        match <[u8; 4]>::from(*self) {
+            [r, g, b, 255] => Rgb::from([r, g, b]).to_hex(uppercase, shorthand),
+            bytes => super::rgb::format_hex(&bytes, uppercase, shorthand),
+        }
+    }
+
+    /// Composites this color over `background` with the Porter-Duff source-over operator.
+    ///
+    /// Both colors and the result use straight (non-premultiplied) alpha.
+    /// If both colors are fully transparent, the result is transparent black.
+    /// See [`Rgb::composite_parts`] if you need the color and alpha separately.
+    #[must_use]
+    pub fn over(self, background: Rgba) -> Rgba {
+        let (Rgb { r, g, b }, alpha) = Rgb::composite_parts(self, background);
+
+        Self { r, g, b, alpha }
+    }
+
+    /// Composites this color over every pixel of `buffer` in place, as with [`Rgba::over`].
+    /// This is the fill operation for drawing a translucent overlay.
+    ///
+    /// If `linear` is `true` the colors are blended in linear light, which is physically correct,
+    /// otherwise they are blended in the gamma-encoded space, which is faster but makes
+    /// mixtures of different hues look darker than they should.
+    ///
+    /// A fully transparent color leaves the buffer unchanged, and an opaque color overwrites it.
+    pub fn composite_over_slice(self, buffer: &mut [Rgba], linear: bool) {
+        match self.alpha {
+            alpha if alpha <= 0.0 => {}
+            alpha if alpha >= 1.0 => buffer.fill(self),
+            _ if linear => {
+                let top = self.map_channels(srgb_to_linear, false);
+
+                for pixel in buffer {
+                    *pixel = top
+                        .over(pixel.map_channels(srgb_to_linear, false))
+                        .map_channels(linear_to_srgb, false);
+                }
+            }
+            _ => {
+                for pixel in buffer {
+                    *pixel = self.over(*pixel);
+                }
+            }
+        }
+    }
+
+    /// Returns the color with `function` applied to the red, green, and blue channels,
+    /// and to the alpha channel only if `include_alpha` is `true`. This is useful for applying
+    /// a transfer function or quantization to every channel, such as sRGB gamma decoding.
+    #[must_use]
+    pub fn map_channels(self, function: impl Fn(f64) -> f64, include_alpha: bool) -> Self {
+        Self {
+            r: function(self.r),
+            g: function(self.g),
+            b: function(self.b),
+            alpha: if include_alpha {
+                function(self.alpha)
+            } else {
+                self.alpha
+            },
+        }
+    }
+
+    /// Returns the channels as straight-alpha 8-bit sRGB, as written to a PNG file.
+    /// Each channel is clamped to `0.0..=1.0` and rounded to the nearest byte, with halves rounded up,
+    /// so that `0.5` becomes `128`.
+    ///
+    /// This assumes the color has straight alpha, as everywhere else in this crate.
+    /// For premultiplied colors, such as read back from a framebuffer, see [`PremulRgba::to_straight_srgb8`].
+    pub fn to_straight_srgb8(&self) -> [u8; 4] {
+        <[f64; 4]>::from(*self).map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Converts this color from straight alpha to premultiplied alpha,
+    /// multiplying each color channel by the alpha channel.
+    ///
+    /// The input is assumed to be straight (non-premultiplied), as every other function in this
+    /// crate expects, but the returned color is **premultiplied**. Pass it to
+    /// [`Rgba::unpremultiply`] before using it with anything else in this crate.
+    #[must_use]
+    pub fn premultiply(self) -> Rgba {
+        Self {
+            r: self.r * self.alpha,
+            g: self.g * self.alpha,
+            b: self.b * self.alpha,
+            alpha: self.alpha,
+        }
+    }
+
+    /// The inverse of [`Rgba::premultiply`], this divides each color channel by the alpha channel.
+    ///
+    /// The input is assumed to be **premultiplied**, and the returned color is straight
+    /// (non-premultiplied) alpha. If the alpha channel is `0.0`, the color channels are
+    /// unrecoverable and the result is transparent black.
+    #[must_use]
+    pub fn unpremultiply(self) -> Rgba {
+        if self.alpha == 0.0 {
+            return Self::from([0.0, 0.0, 0.0, 0.0]);
+        }
+
+        Self {
+            r: self.r / self.alpha,
+            g: self.g / self.alpha,
+            b: self.b / self.alpha,
+            alpha: self.alpha,
+        }
+    }
+}
+
 //
 // Implement to/from primitives
 //
@@ -117,23 +286,34 @@ impl From<Rgba> for [f64; 4] {
     }
 }
 
-impl From<&str> for Rgba {
-    /// Takes a hexadecimal string optionally prefixed with a `#` and returns an [`Rgba`] structure.
-    /// The input is unchecked and will panic if the input has invalid hexadecimal characters.
-    /// For a more robust constructor that won't panic, see [`crate::Color::new`].
-    fn from(string: &str) -> Self {
-        let string = string.strip_prefix('#').unwrap_or(string);
+impl TryFrom<&str> for Rgba {
+    type Error = css::Error;
 
-        const EXPECT_MSG: &str = "invalid hexadecimal string";
+    /// Takes a six or eight-digit hexadecimal string optionally prefixed with a `#` and returns an
+    /// [`Rgba`] structure. If there are only six digits, the color will be opaque.
+    /// Returns [`css::Error::InvalidHexChars`] or [`css::Error::InvalidHexLength`] if the input is invalid.
+    fn try_from(string: &str) -> css::Result<Self> {
+        let string = string.strip_prefix('#').unwrap_or(string);
 
-        let Rgb { r, g, b } = Rgb::from(string);
-        let alpha = u8::from_str_radix(&string[6..8], 16).expect(EXPECT_MSG);
+        if !string.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(css::Error::InvalidHexChars);
+        }
 
-        Self {
-            r: r as f64 / 255.0,
-            g: g as f64 / 255.0,
-            b: b as f64 / 255.0,
-            alpha: alpha as f64 / 255.0,
+        match string.len() {
+            6 => Ok(Rgb::try_from(string)?.into()),
+            8 => {
+                let Rgb { r, g, b } = Rgb::try_from(&string[0..6])?;
+                // The characters were checked above, so this can not fail.
+                let alpha = u8::from_str_radix(&string[6..8], 16).unwrap();
+
+                Ok(Self {
+                    r,
+                    g,
+                    b,
+                    alpha: alpha as f64 / 255.0,
+                })
+            }
+            _ => Err(css::Error::InvalidHexLength),
         }
     }
 }
@@ -200,16 +380,10 @@ impl TryFrom<&css::CssColorNotation> for Rgba {
 
     fn try_from(other: &css::CssColorNotation) -> css::Result<Self> {
         match other.format {
-            css::CssColorType::Rgb => Ok(Self::from(Rgb::try_from(other)?)),
-            css::CssColorType::Rgba => {
-                let mut this = Self::from(Rgb::try_from(other)?);
-
-                this.alpha = css::css_number_to_float(
-                    other.values.get(3).ok_or(css::Error::InvalidCssParams)?,
-                );
-
-                Ok(this)
-            }
+            css::CssColorType::Rgb | css::CssColorType::Rgba => Ok(Self {
+                alpha: css::css_optional_alpha(other),
+                ..Self::from(Rgb::try_from(other)?)
+            }),
             _ => Err(css::Error::WrongCssFormat),
         }
     }
@@ -217,15 +391,7 @@ impl TryFrom<&css::CssColorNotation> for Rgba {
 
 impl From<Rgba> for css::CssColorNotation {
     fn from(other: Rgba) -> Self {
-        Self {
-            format: css::CssColorType::Rgba,
-            values: vec![
-                css::CssNumber::Float(other.r * 255.0),
-                css::CssNumber::Float(other.g * 255.0),
-                css::CssNumber::Float(other.b * 255.0),
-                css::CssNumber::Percent(other.alpha),
-            ],
-        }
+        Self::rgba(other.r, other.g, other.b, other.alpha)
     }
 }
 
@@ -251,3 +417,144 @@ impl From<Rgba> for wgpu::Color {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_try_from_str() {
+        assert_eq!(
+            Rgba::try_from("#33669980").unwrap(),
+            Rgba::from([51_u8, 102, 153, 128])
+        );
+        assert_eq!(
+            Rgba::try_from("#336699").unwrap(),
+            Rgba::from([51_u8, 102, 153, 255])
+        );
+        assert!(matches!(
+            Rgba::try_from("#3366zz80"),
+            Err(css::Error::InvalidHexChars)
+        ));
+        assert!(matches!(
+            Rgba::try_from("#3366998"),
+            Err(css::Error::InvalidHexLength)
+        ));
+    }
+
+    #[test]
+    fn test_hex_formatting() {
+        let color = Rgba::from([255, 128, 0, 64]);
+
+        assert_eq!(format!("{:x}", color), "ff800040");
+        assert_eq!(format!("{:X}", color), "FF800040");
+        assert_eq!(format!("{:#x}", color), "#ff800040");
+        assert_eq!(format!("{:#X}", color), "#FF800040");
+    }
+
+    #[test]
+    fn test_over_opaque() {
+        let red = Rgba::from([1.0, 0.0, 0.0, 0.5]);
+        let white = Rgba::from([1.0, 1.0, 1.0, 1.0]);
+
+        assert_eq!(red.over(white), Rgba::from([1.0, 0.5, 0.5, 1.0]));
+    }
+
+    #[test]
+    fn test_over_transparent() {
+        let clear = Rgba::from([1.0, 0.0, 0.0, 0.0]);
+        let over = clear.over(Rgba::from([0.0, 1.0, 0.0, 0.0]));
+
+        assert_eq!(over, Rgba::from([0.0, 0.0, 0.0, 0.0]));
+    }
+
+    #[test_case(false ; "encoded")]
+    #[test_case(true ; "linear")]
+    fn test_composite_over_slice_extremes(linear: bool) {
+        let original = vec![
+            Rgba::from([0.2, 0.4, 0.6, 0.5]),
+            Rgba::from([1.0, 1.0, 1.0, 1.0]),
+        ];
+        let mut buffer = original.clone();
+
+        Rgba::from([1.0, 0.0, 0.0, 0.0]).composite_over_slice(&mut buffer, linear);
+        assert_eq!(buffer, original);
+
+        let red = Rgba::from([1.0, 0.0, 0.0, 1.0]);
+
+        red.composite_over_slice(&mut buffer, linear);
+        assert_eq!(buffer, vec![red; 2]);
+    }
+
+    #[test]
+    fn test_composite_over_slice_linear() {
+        let mut encoded = vec![Rgba::from([0.0, 1.0, 0.0, 1.0])];
+        let mut linear = encoded.clone();
+        let red = Rgba::from([1.0, 0.0, 0.0, 0.5]);
+
+        red.composite_over_slice(&mut encoded, false);
+        red.composite_over_slice(&mut linear, true);
+
+        assert_eq!(encoded[0], Rgba::from([0.5, 0.5, 0.0, 1.0]));
+        // Blending in linear light avoids the dark band between hues
+        assert_eq!(linear[0].rgba_array(), [188, 188, 0, 255]);
+    }
+
+    #[test_case(false => [0.5, 0.25, 1.0, 0.3] ; "without alpha")]
+    #[test_case(true => [0.5, 0.25, 1.0, 0.25] ; "with alpha")]
+    fn test_map_channels(include_alpha: bool) -> [f64; 4] {
+        let color = Rgba::from([0.45, 0.3, 0.9, 0.3]);
+        let quantize = |channel: f64| (channel * 4.0).round() / 4.0;
+
+        color.map_channels(quantize, include_alpha).into()
+    }
+
+    #[test_case([1.0, 0.5, 0.2, 0.5] => [255, 128, 51, 128] ; "half alpha")]
+    #[test_case([1.2, -0.1, 0.998, 0.0] => [255, 0, 254, 0] ; "clamped")]
+    fn test_to_straight_srgb8(channels: [f64; 4]) -> [u8; 4] {
+        Rgba::from(channels).to_straight_srgb8()
+    }
+
+    #[test]
+    fn test_into_iterator() {
+        let color = Rgba::from([0.1, 0.2, 0.3, 0.4]);
+
+        assert_eq!(color.into_iter().sum::<f64>(), 1.0);
+        assert!(color.into_iter().eq(color.component_values()));
+    }
+
+    #[test]
+    fn test_premultiply_round_trip() {
+        let color = Rgba::from([0.2, 0.4, 0.6, 0.3]);
+        let premultiplied = color.premultiply();
+
+        assert!((premultiplied.g - 0.12).abs() < 1e-12);
+
+        let round_trip: [f64; 4] = premultiplied.unpremultiply().into();
+
+        for (expected, actual) in <[f64; 4]>::from(color).iter().zip(round_trip) {
+            assert!(
+                (expected - actual).abs() < 1e-12,
+                "{color:?} != {round_trip:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unpremultiply_transparent() {
+        let color = Rgba::from([0.2, 0.4, 0.6, 0.0]);
+
+        assert_eq!(color.unpremultiply(), Rgba::from([0.0, 0.0, 0.0, 0.0]));
+    }
+
+    #[test_case([255, 0, 0, 255], false => "#FF0000" ; "opaque")]
+    #[test_case([255, 0, 0, 128], false => "#FF000080" ; "translucent")]
+    #[test_case([255, 0, 0, 0], false => "#FF000000" ; "transparent")]
+    #[test_case([255, 0, 0, 255], true => "#F00" ; "opaque shorthand")]
+    #[test_case([255, 0, 0, 0x88], true => "#F008" ; "translucent shorthand")]
+    #[test_case([255, 0, 0, 128], true => "#FF000080" ; "not collapsible")]
+    fn test_to_hex(array: [u8; 4], shorthand: bool) -> String {
+        Rgba::from(array).to_hex(true, shorthand)
+    }
+}
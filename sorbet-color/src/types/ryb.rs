@@ -0,0 +1,243 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::types::*;
+
+/// This structure represents colors in the subtractive RYB color model with
+/// red, yellow, and blue pigment channels, as used on the traditional artist's color wheel.
+/// See the [Wikipedia reference](<https://en.wikipedia.org/wiki/RYB_color_model>) for details.
+///
+/// A value of `0.0` in every channel is white (no pigment), and `1.0` in every channel is black.
+///
+/// Conversion to [`Rgb`] uses the trilinear interpolation cube described by
+/// Gosset and Chen in *Paint Inspired Color Compositing*.
+/// There is no closed-form inverse of that cube, so the conversion from [`Rgb`] interpolates
+/// an approximate inverse cube instead; round-tripping is therefore not exact.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ryb {
+    /// Red pigment channel.
+    /// Ranged `0.0..1.0`.
+    pub r: f64,
+    /// Yellow pigment channel.
+    /// Ranged `0.0..1.0`.
+    pub y: f64,
+    /// Blue pigment channel.
+    /// Ranged `0.0..1.0`.
+    pub b: f64,
+}
+
+impl Eq for Ryb {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for Ryb {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.r.to_bits().hash(state);
+        self.y.to_bits().hash(state);
+        self.b.to_bits().hash(state);
+    }
+}
+
+impl Ryb {
+    /// Returns the color with its hue rotated by `degrees` on the artist's RYB color wheel,
+    /// where red, yellow, and blue are a third of the wheel apart, keeping the amount of pigment.
+    ///
+    /// This treats the pigment channels like the channels of [`Rgb`] and rotates the hue as [`Hsv`] does.
+    /// See [`Hsl::rotate_hue`] for the same on the RGB color wheel.
+    #[must_use]
+    pub fn rotate_hue(self, degrees: f64) -> Self {
+        let hsv = Hsv::from(Rgb::from(<[f64; 3]>::from(self)));
+
+        Self::from(<[f64; 3]>::from(Rgb::from(hsv.rotate_hue(degrees))))
+    }
+
+    /// Returns the complementary color on the RYB color wheel, as taught in painting,
+    /// where the complement of blue is orange rather than yellow.
+    #[must_use]
+    pub fn complement(self) -> Self {
+        self.rotate_hue(180.0)
+    }
+
+    /// Returns this color and the two colors a third of the RYB color wheel away from it, in that order.
+    /// The triad of red is yellow and blue.
+    pub fn triad(self) -> [Self; 3] {
+        [self, self.rotate_hue(120.0), self.rotate_hue(240.0)]
+    }
+
+    /// Returns this color between the colors `spread` degrees to either side of it on the RYB color wheel.
+    /// See [`Hsl::analogous`].
+    pub fn analogous(self, spread: f64) -> [Self; 3] {
+        [self.rotate_hue(-spread), self, self.rotate_hue(spread)]
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for Ryb {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            r: array[0],
+            y: array[1],
+            b: array[2],
+        }
+    }
+}
+
+impl From<Ryb> for [f64; 3] {
+    fn from(color: Ryb) -> Self {
+        [color.r, color.y, color.b]
+    }
+}
+
+//
+// Implement to/from Rgb
+//
+
+impl From<Rgb> for Ryb {
+    fn from(other: Rgb) -> Self {
+        Self::from(trilinear(&RGB_TO_RYB, other.r, other.g, other.b))
+    }
+}
+
+impl From<Ryb> for Rgb {
+    fn from(other: Ryb) -> Self {
+        Self::from(trilinear(&RYB_TO_RGB, other.r, other.y, other.b))
+    }
+}
+
+//
+// Math helpers
+//
+
+/// The corners of the RYB cube in RGB, indexed by the bits `0bBYR` of the RYB corner.
+/// From Gosset and Chen, *Paint Inspired Color Compositing*.
+const RYB_TO_RGB: [[f64; 3]; 8] = [
+    [1.0, 1.0, 1.0],     // white
+    [1.0, 0.0, 0.0],     // red
+    [1.0, 1.0, 0.0],     // yellow
+    [1.0, 0.5, 0.0],     // orange
+    [0.163, 0.373, 0.6], // blue
+    [0.5, 0.0, 0.5],     // purple
+    [0.0, 0.66, 0.2],    // green
+    [0.2, 0.094, 0.0],   // black
+];
+
+/// The corners of the RGB cube in RYB, indexed by the bits `0bBGR` of the RGB corner.
+/// This is an approximate inverse of [`RYB_TO_RGB`].
+const RGB_TO_RYB: [[f64; 3]; 8] = [
+    [1.0, 1.0, 1.0], // black
+    [1.0, 0.0, 0.0], // red
+    [0.0, 1.0, 1.0], // green
+    [0.0, 1.0, 0.0], // yellow
+    [0.0, 0.0, 1.0], // blue
+    [1.0, 0.0, 1.0], // magenta
+    [0.0, 0.5, 1.0], // cyan
+    [0.0, 0.0, 0.0], // white
+];
+
+fn trilinear(cube: &[[f64; 3]; 8], x: f64, y: f64, z: f64) -> [f64; 3] {
+    let mut out = [0.0; 3];
+
+    for (index, corner) in cube.iter().enumerate() {
+        let wx = if index & 0b001 == 0 { 1.0 - x } else { x };
+        let wy = if index & 0b010 == 0 { 1.0 - y } else { y };
+        let wz = if index & 0b100 == 0 { 1.0 - z } else { z };
+        let weight = wx * wy * wz;
+
+        for (channel, value) in out.iter_mut().zip(corner) {
+            *channel += weight * value;
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ryb_primaries_to_rgb() {
+        assert_eq!(
+            Rgb::from(Ryb::from([0.0, 0.0, 0.0])),
+            Rgb::from([1.0, 1.0, 1.0])
+        );
+        assert_eq!(
+            Rgb::from(Ryb::from([1.0, 0.0, 0.0])),
+            Rgb::from([1.0, 0.0, 0.0])
+        );
+        assert_eq!(
+            Rgb::from(Ryb::from([0.0, 1.0, 0.0])),
+            Rgb::from([1.0, 1.0, 0.0])
+        );
+    }
+
+    // Red and yellow pigment together should give orange
+    #[test]
+    fn test_ryb_red_yellow_mix_is_orange() {
+        let Rgb { r, g, b } = Rgb::from(Ryb::from([1.0, 1.0, 0.0]));
+
+        assert!(r > 0.9, "red channel was {r}");
+        assert!((0.3..0.7).contains(&g), "green channel was {g}");
+        assert!(b < 0.1, "blue channel was {b}");
+    }
+
+    #[test]
+    fn test_complement_of_blue_is_orange() {
+        let blue = Ryb::from(Rgb::from([0.0, 0.0, 1.0]));
+        let Rgb { r, g, b } = Rgb::from(blue.complement());
+
+        assert!(r > 0.9, "red channel was {r}");
+        assert!((0.3..0.7).contains(&g), "green channel was {g}");
+        assert!(b < 0.1, "blue channel was {b}");
+    }
+
+    #[test]
+    fn test_triad_of_red_is_primaries() {
+        let triad = Ryb::from([1.0, 0.0, 0.0]).triad();
+
+        assert_eq!(
+            triad.map(<[f64; 3]>::from),
+            [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+        );
+    }
+
+    #[test]
+    fn test_analogous() {
+        let [before, color, after] = Ryb::from([0.0, 1.0, 0.0]).analogous(60.0);
+
+        assert_eq!(color, Ryb::from([0.0, 1.0, 0.0]));
+        assert_eq!(<[f64; 3]>::from(before), [1.0, 1.0, 0.0]);
+        assert_eq!(<[f64; 3]>::from(after), [0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_rgb_primaries_to_ryb() {
+        assert_eq!(
+            Ryb::from(Rgb::from([1.0, 0.0, 0.0])),
+            Ryb::from([1.0, 0.0, 0.0])
+        );
+        assert_eq!(
+            Ryb::from(Rgb::from([1.0, 1.0, 0.0])),
+            Ryb::from([0.0, 1.0, 0.0])
+        );
+        assert_eq!(
+            Ryb::from(Rgb::from([1.0, 1.0, 1.0])),
+            Ryb::from([0.0, 0.0, 0.0])
+        );
+    }
+}
@@ -0,0 +1,165 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    gamut::{linear_to_srgb, srgb_to_linear},
+    types::*,
+    Color,
+};
+
+/// This structure represents colors in the CIE 1931 XYZ color space,
+/// relative to the D65 white point used by sRGB.
+/// See the [Wikipedia reference](<https://en.wikipedia.org/wiki/CIE_1931_color_space>) for details.
+///
+/// The `y` channel is the relative luminance, where `1.0` is the luminance of the white point.
+/// Conversions to and from [`Rgb`] go through linear-light sRGB.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Xyz {
+    /// The X tristimulus value.
+    /// Ranged `0.0..0.9505` for colors inside of sRGB.
+    pub x: f64,
+    /// The Y tristimulus value, the relative luminance.
+    /// Ranged `0.0..1.0` for colors inside of sRGB.
+    pub y: f64,
+    /// The Z tristimulus value.
+    /// Ranged `0.0..1.0891` for colors inside of sRGB.
+    pub z: f64,
+}
+
+impl Eq for Xyz {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for Xyz {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.x.to_bits().hash(state);
+        self.y.to_bits().hash(state);
+        self.z.to_bits().hash(state);
+    }
+}
+
+impl Color for Xyz {
+    fn hex(&self) -> String {
+        Rgb::from(*self).hex()
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for Xyz {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            x: array[0],
+            y: array[1],
+            z: array[2],
+        }
+    }
+}
+
+impl From<Xyz> for [f64; 3] {
+    fn from(color: Xyz) -> Self {
+        [color.x, color.y, color.z]
+    }
+}
+
+//
+// Implement From for all other Color types
+//
+
+impl From<Rgb> for Xyz {
+    fn from(other: Rgb) -> Self {
+        let linear = [
+            srgb_to_linear(other.r),
+            srgb_to_linear(other.g),
+            srgb_to_linear(other.b),
+        ];
+
+        Self::from(multiply(&RGB_TO_XYZ, linear))
+    }
+}
+
+impl From<Xyz> for Rgb {
+    fn from(other: Xyz) -> Self {
+        let [r, g, b] = multiply(&XYZ_TO_RGB, [other.x, other.y, other.z]);
+
+        Self {
+            r: linear_to_srgb(r),
+            g: linear_to_srgb(g),
+            b: linear_to_srgb(b),
+        }
+    }
+}
+
+impl_from_via!(Rgb: Rgba, Hsv, Hsva, Hsl, Hsla => Xyz);
+impl_from_via!(Rgb: Xyz => Rgba, Hsv, Hsva, Hsl, Hsla);
+
+//
+// Math helpers
+//
+
+/// Linear sRGB to XYZ, derived from the sRGB primaries and the D65 white point `(0.3127, 0.3290)`.
+const RGB_TO_XYZ: [[f64; 3]; 3] = [
+    [0.4123907992659595, 0.35758433938387796, 0.1804807884018343],
+    [0.21263900587151036, 0.7151686787677559, 0.07219231536073371],
+    [0.01933081871559185, 0.11919477979462599, 0.9505321522496606],
+];
+
+/// The inverse of [`RGB_TO_XYZ`].
+const XYZ_TO_RGB: [[f64; 3]; 3] = [
+    [3.2409699419045213, -1.5373831775700935, -0.4986107602930033],
+    [-0.9692436362808798, 1.8759675015077206, 0.04155505740717561],
+    [
+        0.05563007969699361,
+        -0.20397695888897657,
+        1.0569715142428786,
+    ],
+];
+
+pub(super) fn multiply(matrix: &[[f64; 3]; 3], vector: [f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case([0.0, 0.0, 0.0] ; "black")]
+    #[test_case([1.0, 1.0, 1.0] ; "white")]
+    #[test_case([1.0, 0.0, 0.0] ; "red")]
+    #[test_case([0.2, 0.4, 0.6] ; "steel blue")]
+    #[test_case([0.9, 0.75, 0.01] ; "amber")]
+    fn test_rgb_xyz_round_trip(rgb: [f64; 3]) {
+        let round_trip: [f64; 3] = Rgb::from(Xyz::from(Rgb::from(rgb))).into();
+
+        for (expected, actual) in rgb.iter().zip(round_trip) {
+            assert!(
+                (expected - actual).abs() < 1e-9,
+                "{rgb:?} != {round_trip:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_white_is_d65() {
+        let Xyz { x, y, z } = Xyz::from(Rgb::from([1.0, 1.0, 1.0]));
+
+        assert!((x - 0.9505).abs() < 1e-4, "x was {x}");
+        assert!((y - 1.0).abs() < 1e-9, "y was {y}");
+        assert!((z - 1.0891).abs() < 1e-4, "z was {z}");
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::types::*;
+
+/// This structure represents colors in the YCbCr model used by digital video, with a luma
+/// channel and the blue-difference and red-difference chroma channels.
+/// See the [Wikipedia reference](<https://en.wikipedia.org/wiki/YCbCr>) for details.
+///
+/// The channels are full-range: luma is ranged `0.0..1.0` and the chroma channels are
+/// ranged `-0.5..0.5`, centered on zero. Encoders that expect the 8-bit limited range
+/// can use [`Yuv::to_limited_range`], which shares the same channels.
+///
+/// The conversions to and from [`Rgb`] depend on a [`LumaStandard`], either Rec. 601 or Rec. 709.
+/// The [`From`] implementations use [`LumaStandard::Bt709`]; use [`YCbCr::from_rgb`] and
+/// [`YCbCr::to_rgb`] to choose another standard.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct YCbCr {
+    /// Luma channel.
+    /// Ranged `0.0..1.0`.
+    pub y: f64,
+    /// Blue-difference chroma channel.
+    /// Ranged `-0.5..0.5`.
+    pub cb: f64,
+    /// Red-difference chroma channel.
+    /// Ranged `-0.5..0.5`.
+    pub cr: f64,
+}
+
+impl Eq for YCbCr {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for YCbCr {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.y.to_bits().hash(state);
+        self.cb.to_bits().hash(state);
+        self.cr.to_bits().hash(state);
+    }
+}
+
+impl YCbCr {
+    /// Converts a color from [`Rgb`] using the coefficients of `standard`.
+    pub fn from_rgb(rgb: Rgb, standard: LumaStandard) -> Self {
+        Yuv::from_rgb(rgb, standard).into()
+    }
+
+    /// Converts the color to [`Rgb`] using the coefficients of `standard`.
+    /// This is the inverse of [`YCbCr::from_rgb`] with the same standard.
+    pub fn to_rgb(self, standard: LumaStandard) -> Rgb {
+        Yuv::from(self).to_rgb(standard)
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for YCbCr {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            y: array[0],
+            cb: array[1],
+            cr: array[2],
+        }
+    }
+}
+
+impl From<YCbCr> for [f64; 3] {
+    fn from(color: YCbCr) -> Self {
+        [color.y, color.cb, color.cr]
+    }
+}
+
+//
+// Implement to/from Yuv
+//
+
+impl From<Yuv> for YCbCr {
+    fn from(other: Yuv) -> Self {
+        Self {
+            y: other.y,
+            cb: other.u,
+            cr: other.v,
+        }
+    }
+}
+
+impl From<YCbCr> for Yuv {
+    fn from(other: YCbCr) -> Self {
+        Self {
+            y: other.y,
+            u: other.cb,
+            v: other.cr,
+        }
+    }
+}
+
+//
+// Implement to/from Rgb
+//
+
+impl From<Rgb> for YCbCr {
+    fn from(other: Rgb) -> Self {
+        Self::from_rgb(other, LumaStandard::default())
+    }
+}
+
+impl From<YCbCr> for Rgb {
+    fn from(other: YCbCr) -> Self {
+        other.to_rgb(LumaStandard::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(LumaStandard::Bt601, [1.0, 0.0, 0.0] => [0.299, -0.1687, 0.5] ; "bt601 red")]
+    #[test_case(LumaStandard::Bt601, [0.0, 1.0, 0.0] => [0.587, -0.3313, -0.4187] ; "bt601 green")]
+    #[test_case(LumaStandard::Bt601, [0.0, 0.0, 1.0] => [0.114, 0.5, -0.0813] ; "bt601 blue")]
+    #[test_case(LumaStandard::Bt709, [1.0, 0.0, 0.0] => [0.2126, -0.1146, 0.5] ; "bt709 red")]
+    #[test_case(LumaStandard::Bt709, [0.0, 1.0, 0.0] => [0.7152, -0.3854, -0.4542] ; "bt709 green")]
+    #[test_case(LumaStandard::Bt709, [0.0, 0.0, 1.0] => [0.0722, 0.5, -0.0458] ; "bt709 blue")]
+    fn test_from_rgb(standard: LumaStandard, rgb: [f64; 3]) -> [f64; 3] {
+        <[f64; 3]>::from(YCbCr::from_rgb(Rgb::from(rgb), standard)).map(|x| (x * 1e4).round() / 1e4)
+    }
+
+    #[test_case([1.0, 1.0, 1.0] => [1.0, 0.0, 0.0] ; "white")]
+    #[test_case([0.0, 0.0, 0.0] => [0.0, 0.0, 0.0] ; "black")]
+    fn test_from_rgb_default(rgb: [f64; 3]) -> [f64; 3] {
+        <[f64; 3]>::from(YCbCr::from(Rgb::from(rgb))).map(|x| (x * 1e12).round() / 1e12)
+    }
+
+    #[test_case(LumaStandard::Bt601 ; "bt601")]
+    #[test_case(LumaStandard::Bt709 ; "bt709")]
+    fn test_round_trip(standard: LumaStandard) {
+        for rgb in [
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.2, 0.4, 0.6],
+        ] {
+            let round_trip = YCbCr::from_rgb(Rgb::from(rgb), standard).to_rgb(standard);
+
+            for (expected, actual) in rgb.iter().zip(<[f64; 3]>::from(round_trip)) {
+                assert!(
+                    (expected - actual).abs() < 1e-12,
+                    "{rgb:?} != {round_trip:?}"
+                );
+            }
+        }
+    }
+}
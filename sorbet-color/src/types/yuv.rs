@@ -0,0 +1,211 @@
+/*
+ * Copyright 2022 Jacob Birkett
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::types::*;
+
+/// The luma coefficients of a video standard, which decide how much each of the red, green,
+/// and blue channels contributes to the luma of a [`Yuv`] color.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LumaStandard {
+    /// ITU-R BT.601, used by standard-definition video.
+    Bt601,
+    /// ITU-R BT.709, used by high-definition video and by the [`From`] conversions of [`Yuv`].
+    #[default]
+    Bt709,
+}
+
+impl LumaStandard {
+    /// Returns the `(kr, kb)` luma coefficients of the standard.
+    /// The green coefficient is `1.0 - kr - kb`.
+    pub fn coefficients(self) -> (f64, f64) {
+        match self {
+            Self::Bt601 => (0.299, 0.114),
+            Self::Bt709 => (0.2126, 0.0722),
+        }
+    }
+}
+
+/// This structure represents colors as luma and two chroma-difference channels,
+/// as used by video encoders. See the [Wikipedia reference](<https://en.wikipedia.org/wiki/YUV>) for details.
+///
+/// The channels are full-range: luma is ranged `0.0..1.0` and the chroma channels are
+/// centered on zero. For the 8-bit limited range ("studio swing") used by most video,
+/// see [`Yuv::to_limited_range`].
+///
+/// The conversions to and from [`Rgb`] depend on a [`LumaStandard`], and operate on the
+/// gamma-encoded channels. The [`From`] implementations use [`LumaStandard::Bt709`];
+/// use [`Yuv::from_rgb`] and [`Yuv::to_rgb`] to choose another standard.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Yuv {
+    /// Luma channel.
+    /// Ranged `0.0..1.0`.
+    pub y: f64,
+    /// Blue-difference chroma channel.
+    /// Ranged `-0.5..0.5`.
+    pub u: f64,
+    /// Red-difference chroma channel.
+    /// Ranged `-0.5..0.5`.
+    pub v: f64,
+}
+
+impl Eq for Yuv {}
+
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for Yuv {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.y.to_bits().hash(state);
+        self.u.to_bits().hash(state);
+        self.v.to_bits().hash(state);
+    }
+}
+
+impl Yuv {
+    /// Converts a color from [`Rgb`] using the coefficients of `standard`.
+    pub fn from_rgb(rgb: Rgb, standard: LumaStandard) -> Self {
+        let (kr, kb) = standard.coefficients();
+        let y = kr * rgb.r + (1.0 - kr - kb) * rgb.g + kb * rgb.b;
+
+        Self {
+            y,
+            u: 0.5 * (rgb.b - y) / (1.0 - kb),
+            v: 0.5 * (rgb.r - y) / (1.0 - kr),
+        }
+    }
+
+    /// Converts the color to [`Rgb`] using the coefficients of `standard`.
+    /// This is the inverse of [`Yuv::from_rgb`] with the same standard.
+    pub fn to_rgb(self, standard: LumaStandard) -> Rgb {
+        let (kr, kb) = standard.coefficients();
+        let r = self.y + 2.0 * (1.0 - kr) * self.v;
+        let b = self.y + 2.0 * (1.0 - kb) * self.u;
+        let g = (self.y - kr * r - kb * b) / (1.0 - kr - kb);
+
+        Rgb { r, g, b }
+    }
+
+    /// Returns the color as 8-bit limited-range ("studio swing") `[y, u, v]` values,
+    /// where luma is offset to `16..=235` and the chroma channels to `16..=240`, centered on `128`.
+    /// Values outside of those ranges are clamped.
+    pub fn to_limited_range(&self) -> [u8; 3] {
+        [
+            (16.0 + 219.0 * self.y).round().clamp(16.0, 235.0) as u8,
+            (128.0 + 224.0 * self.u).round().clamp(16.0, 240.0) as u8,
+            (128.0 + 224.0 * self.v).round().clamp(16.0, 240.0) as u8,
+        ]
+    }
+
+    /// The inverse of [`Yuv::to_limited_range`], this takes 8-bit limited-range `[y, u, v]` values
+    /// and returns the full-range color.
+    pub fn from_limited_range(array: [u8; 3]) -> Self {
+        Self {
+            y: (array[0] as f64 - 16.0) / 219.0,
+            u: (array[1] as f64 - 128.0) / 224.0,
+            v: (array[2] as f64 - 128.0) / 224.0,
+        }
+    }
+}
+
+//
+// Implement to/from primitives
+//
+
+impl From<[f64; 3]> for Yuv {
+    fn from(array: [f64; 3]) -> Self {
+        Self {
+            y: array[0],
+            u: array[1],
+            v: array[2],
+        }
+    }
+}
+
+impl From<Yuv> for [f64; 3] {
+    fn from(color: Yuv) -> Self {
+        [color.y, color.u, color.v]
+    }
+}
+
+//
+// Implement to/from Rgb
+//
+
+impl From<Rgb> for Yuv {
+    fn from(other: Rgb) -> Self {
+        Self::from_rgb(other, LumaStandard::default())
+    }
+}
+
+impl From<Yuv> for Rgb {
+    fn from(other: Yuv) -> Self {
+        other.to_rgb(LumaStandard::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(LumaStandard::Bt601 ; "bt601")]
+    #[test_case(LumaStandard::Bt709 ; "bt709")]
+    fn test_white(standard: LumaStandard) {
+        let Yuv { y, u, v } = Rgb::from([1.0, 1.0, 1.0]).to_yuv(standard);
+
+        assert!((y - 1.0).abs() < 1e-12, "y was {y}");
+        assert!(u.abs() < 1e-12, "u was {u}");
+        assert!(v.abs() < 1e-12, "v was {v}");
+    }
+
+    #[test_case(LumaStandard::Bt601, [1.0, 0.0, 0.0] => [0.299, -0.1687, 0.5] ; "bt601 red")]
+    #[test_case(LumaStandard::Bt709, [1.0, 0.0, 0.0] => [0.2126, -0.1146, 0.5] ; "bt709 red")]
+    #[test_case(LumaStandard::Bt709, [0.0, 0.0, 1.0] => [0.0722, 0.5, -0.0458] ; "bt709 blue")]
+    fn test_from_rgb(standard: LumaStandard, rgb: [f64; 3]) -> [f64; 3] {
+        <[f64; 3]>::from(Rgb::from(rgb).to_yuv(standard)).map(|x| (x * 1e4).round() / 1e4)
+    }
+
+    #[test_case(LumaStandard::Bt601 ; "bt601")]
+    #[test_case(LumaStandard::Bt709 ; "bt709")]
+    fn test_round_trip(standard: LumaStandard) {
+        let rgb = Rgb::from([0.2, 0.4, 0.6]);
+        let round_trip = Rgb::from_yuv(rgb.to_yuv(standard), standard);
+
+        for (expected, actual) in <[f64; 3]>::from(rgb)
+            .iter()
+            .zip(<[f64; 3]>::from(round_trip))
+        {
+            assert!(
+                (expected - actual).abs() < 1e-12,
+                "{rgb:?} != {round_trip:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_limited_range() {
+        assert_eq!(
+            Yuv::from(Rgb::from([1.0, 1.0, 1.0])).to_limited_range(),
+            [235, 128, 128]
+        );
+        assert_eq!(
+            Yuv::from(Rgb::from([0.0, 0.0, 0.0])).to_limited_range(),
+            [16, 128, 128]
+        );
+        assert_eq!(
+            Yuv::from_limited_range([235, 128, 128]),
+            Yuv::from([1.0, 0.0, 0.0])
+        );
+    }
+}
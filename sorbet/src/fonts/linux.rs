@@ -16,9 +16,15 @@
 
 use super::Error;
 
-use std::path::PathBuf;
+use std::{
+    ffi::{CStr, CString},
+    path::PathBuf,
+};
 
-use fontconfig::Fontconfig;
+use fontconfig::{Fontconfig, ObjectSet, Pattern};
+
+const FC_FAMILY: &[u8] = b"family\0";
+const FC_STYLE: &[u8] = b"style\0";
 
 /// Locate a font on the filesystem by deferring to platform-specific APIs.
 pub fn locate_font<F, S>(family: F, style: Option<S>) -> Result<Option<PathBuf>, Error>
@@ -26,8 +32,59 @@ where
     F: AsRef<str>,
     S: AsRef<str>,
 {
+    Ok(FontSource::new()?.locate(family.as_ref(), style.as_ref().map(S::as_ref)))
+}
+
+/// The initialized platform configuration, to locate many fonts without initializing it each time.
+/// See [`super::FontCache`].
+pub(super) struct FontSource(Fontconfig);
+
+impl FontSource {
+    pub(super) fn new() -> Result<Self, Error> {
+        Fontconfig::new().map(Self).ok_or(Error::FontconfigInit)
+    }
+
+    pub(super) fn locate(&self, family: &str, style: Option<&str>) -> Option<PathBuf> {
+        self.0.find(family, style).map(|font| font.path)
+    }
+}
+
+/// List the family of every installed font, in no particular order and with duplicates.
+/// See [`super::list_families`].
+pub(super) fn family_names() -> Result<Vec<String>, Error> {
     let config = Fontconfig::new().ok_or(Error::FontconfigInit)?;
-    Ok(config
-        .find(family.as_ref(), style.as_ref().map(S::as_ref))
-        .map(|font| font.path))
+    let family = CStr::from_bytes_with_nul(FC_FAMILY).unwrap();
+    let mut objects = ObjectSet::new(&config);
+    objects.add(family);
+
+    let families = fontconfig::list_fonts(&Pattern::new(&config), Some(&objects))
+        .iter()
+        .filter_map(|pattern| pattern.get_string(family).map(String::from))
+        .collect();
+
+    Ok(families)
+}
+
+/// List the style of every installed font of `family`, in no particular order and with duplicates.
+/// See [`super::list_styles`].
+pub(super) fn style_names(family: &str) -> Result<Vec<String>, Error> {
+    let config = Fontconfig::new().ok_or(Error::FontconfigInit)?;
+    let style = CStr::from_bytes_with_nul(FC_STYLE).unwrap();
+    // A family name with a nul character can't be installed.
+    let family = match CString::new(family) {
+        Ok(family) => family,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut pattern = Pattern::new(&config);
+    pattern.add_string(CStr::from_bytes_with_nul(FC_FAMILY).unwrap(), &family);
+    let mut objects = ObjectSet::new(&config);
+    objects.add(style);
+
+    let styles = fontconfig::list_fonts(&pattern, Some(&objects))
+        .iter()
+        .filter_map(|pattern| pattern.get_string(style).map(String::from))
+        .collect();
+
+    Ok(styles)
 }
@@ -13,3 +13,155 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+
+use super::Error;
+
+use std::{os::raw::c_void, path::PathBuf};
+
+use core_foundation::{
+    array::{CFArray, CFArrayRef},
+    base::{CFType, CFTypeRef, TCFType},
+    dictionary::{CFDictionary, CFDictionaryRef},
+    set::{CFSet, CFSetRef},
+    string::{CFString, CFStringRef},
+    url::CFURL,
+};
+
+type CTFontDescriptorRef = *const c_void;
+
+#[link(name = "CoreText", kind = "framework")]
+extern "C" {
+    static kCTFontFamilyNameAttribute: CFStringRef;
+    static kCTFontStyleNameAttribute: CFStringRef;
+    static kCTFontURLAttribute: CFStringRef;
+
+    fn CTFontDescriptorCreateWithAttributes(attributes: CFDictionaryRef) -> CTFontDescriptorRef;
+    fn CTFontDescriptorCreateMatchingFontDescriptors(
+        descriptor: CTFontDescriptorRef,
+        mandatory_attributes: CFSetRef,
+    ) -> CFArrayRef;
+    fn CTFontDescriptorCopyAttribute(
+        descriptor: CTFontDescriptorRef,
+        attribute: CFStringRef,
+    ) -> CFTypeRef;
+    fn CTFontManagerCopyAvailableFontFamilyNames() -> CFArrayRef;
+}
+
+/// Locate a font on the filesystem by deferring to platform-specific APIs.
+///
+/// On macOS the font is matched with Core Text. If the family is installed but has no face
+/// with the requested style, the first face of the family is returned instead,
+/// the same as fontconfig would on Linux.
+pub fn locate_font<F, S>(family: F, style: Option<S>) -> Result<Option<PathBuf>, Error>
+where
+    F: AsRef<str>,
+    S: AsRef<str>,
+{
+    Ok(FontSource::new()?.locate(family.as_ref(), style.as_ref().map(S::as_ref)))
+}
+
+/// Core Text needs no initialization, so this has no state; it exists to match the other platforms.
+/// See [`super::FontCache`].
+pub(super) struct FontSource;
+
+impl FontSource {
+    pub(super) fn new() -> Result<Self, Error> {
+        Ok(Self)
+    }
+
+    pub(super) fn locate(&self, family: &str, style: Option<&str>) -> Option<PathBuf> {
+        let descriptors = match style {
+            Some(style) => matching_descriptors(family, Some(style))
+                .or_else(|| matching_descriptors(family, None)),
+            None => matching_descriptors(family, None),
+        };
+
+        descriptors.and_then(|descriptors| {
+            descriptors.iter().find_map(|descriptor| {
+                let url = unsafe {
+                    CTFontDescriptorCopyAttribute(descriptor.as_CFTypeRef(), kCTFontURLAttribute)
+                };
+                if url.is_null() {
+                    return None;
+                }
+
+                unsafe { CFType::wrap_under_create_rule(url) }
+                    .downcast::<CFURL>()?
+                    .to_path()
+            })
+        })
+    }
+}
+
+/// List the family of every installed font, in no particular order.
+/// See [`super::list_families`].
+pub(super) fn family_names() -> Result<Vec<String>, Error> {
+    let names = unsafe {
+        CFArray::<CFString>::wrap_under_create_rule(CTFontManagerCopyAvailableFontFamilyNames())
+    };
+
+    Ok(names.iter().map(|name| name.to_string()).collect())
+}
+
+/// List the style of every installed font of `family`, in no particular order.
+/// See [`super::list_styles`].
+pub(super) fn style_names(family: &str) -> Result<Vec<String>, Error> {
+    let descriptors = match matching_descriptors(family, None) {
+        Some(descriptors) => descriptors,
+        None => return Ok(Vec::new()),
+    };
+
+    Ok(descriptors
+        .iter()
+        .filter_map(|descriptor| {
+            let style = unsafe {
+                CTFontDescriptorCopyAttribute(descriptor.as_CFTypeRef(), kCTFontStyleNameAttribute)
+            };
+            if style.is_null() {
+                return None;
+            }
+
+            Some(
+                unsafe { CFType::wrap_under_create_rule(style) }
+                    .downcast::<CFString>()?
+                    .to_string(),
+            )
+        })
+        .collect())
+}
+
+/// Returns the descriptors of the installed faces of `family`, and only those with the name `style`
+/// if one is given, or [`None`] if there are none.
+fn matching_descriptors(family: &str, style: Option<&str>) -> Option<CFArray<CFType>> {
+    let (family_key, style_key) = unsafe {
+        (
+            CFString::wrap_under_get_rule(kCTFontFamilyNameAttribute),
+            CFString::wrap_under_get_rule(kCTFontStyleNameAttribute),
+        )
+    };
+
+    let mut pairs = vec![(family_key, CFString::new(family))];
+    if let Some(style) = style {
+        pairs.push((style_key, CFString::new(style)));
+    }
+    // Every requested attribute must match, or Core Text returns the closest font of any family.
+    let keys = pairs.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+    let mandatory = CFSet::from_slice(&keys);
+    let attributes = CFDictionary::from_CFType_pairs(&pairs);
+
+    unsafe {
+        let descriptor = CFType::wrap_under_create_rule(CTFontDescriptorCreateWithAttributes(
+            attributes.as_concrete_TypeRef(),
+        ));
+        let matches = CTFontDescriptorCreateMatchingFontDescriptors(
+            descriptor.as_CFTypeRef(),
+            mandatory.as_concrete_TypeRef(),
+        );
+
+        if matches.is_null() {
+            None
+        } else {
+            Some(CFArray::wrap_under_create_rule(matches))
+        }
+    }
+}
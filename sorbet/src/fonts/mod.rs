@@ -27,6 +27,11 @@ mod platform;
 
 pub use platform::*;
 
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
 use thiserror::Error;
 
 /// The common error type returned from methods in this module. This contains variants that can result from any platform;
@@ -37,12 +42,236 @@ pub enum Error {
     /// this variant will be used. This is used in the event that [`fontconfig::Fontconfig::new()`] returns [`None`].
     #[error("fontconfig could not be initialized")]
     FontconfigInit,
+    /// Reading a font file from the filesystem failed. The original [`std::io::Error`] is available as the source.
+    #[error("failed to read a font file")]
+    Io(#[from] std::io::Error),
+    /// A font file was read successfully, but its contents could not be parsed as a font face.
+    /// The original [`ttf_parser::FaceParsingError`] is available as the source.
+    #[error("failed to parse a font face")]
+    Parse(#[from] ttf_parser::FaceParsingError),
+    /// The requested font family (or style of a family) is not installed on the system.
+    #[error("the requested font could not be found")]
+    NotFound,
+}
+
+/// Returns `true` if the font file at `path` has a glyph for every non-whitespace character in `text`,
+/// according to the font's character map. This is useful for deciding when to fall back to another font,
+/// for example when the primary font lacks a script used in a label.
+///
+/// See [`missing_glyphs`] to find out which character is not covered.
+pub fn covers(path: &Path, text: &str) -> Result<bool, Error> {
+    Ok(missing_glyphs(path, text)?.is_none())
+}
+
+/// Returns the first non-whitespace character in `text` that the font file at `path` has no glyph for,
+/// or [`None`] if every character is covered. See [`covers`].
+pub fn missing_glyphs(path: &Path, text: &str) -> Result<Option<char>, Error> {
+    let data = std::fs::read(path)?;
+    let face = ttf_parser::Face::from_slice(&data, 0)?;
+
+    Ok(text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .find(|&c| face.glyph_index(c).is_none()))
+}
+
+/// Returns the names of every font family installed on the system, such as for a font picker.
+/// The names are sorted case-insensitively and without duplicates.
+pub fn list_families() -> Result<Vec<String>, Error> {
+    Ok(sorted_names(platform::family_names()?))
+}
+
+/// Returns the names of the styles installed for `family`, such as `Regular`, `Bold`, and `Italic`,
+/// to pass to [`locate_font`]. The names are sorted case-insensitively and without duplicates.
+///
+/// If the family is not installed, the list is empty.
+pub fn list_styles<F: AsRef<str>>(family: F) -> Result<Vec<String>, Error> {
+    Ok(sorted_names(platform::style_names(family.as_ref())?))
+}
+
+/// Returns the styles installed for `family`, such as for the style dropdown of a font picker
+/// after the family was chosen. This is [`list_styles`] for a family name that is already a `&str`.
+pub fn styles(family: &str) -> Result<Vec<String>, Error> {
+    list_styles(family)
+}
+
+fn sorted_names(mut names: Vec<String>) -> Vec<String> {
+    names.sort_unstable_by(|a, b| {
+        a.to_lowercase()
+            .cmp(&b.to_lowercase())
+            .then_with(|| a.cmp(b))
+    });
+    names.dedup();
+    names
+}
+
+/// Locates the first font of `families` that is installed, trying each in order, such as
+/// `["Inter", "Helvetica", "sans-serif"]`.
+///
+/// Every family is tried for an exact match (ignoring case) before any is tried for an approximate one,
+/// so an installed "Helvetica" is preferred to "Inter" being mistyped. An approximate match is
+/// the closest installed family by [`match_family`], or whatever the platform substitutes for the family,
+/// such as a generic family like "sans-serif" with fontconfig.
+pub fn locate_font_fallback<S: AsRef<str>>(
+    families: &[S],
+    style: Option<&str>,
+) -> Result<Option<PathBuf>, Error> {
+    let installed = list_families()?;
+    let source = platform::FontSource::new()?;
+
+    let exact = families.iter().map(AsRef::as_ref).find_map(|family| {
+        installed
+            .iter()
+            .find(|name| name.eq_ignore_ascii_case(family))
+            .and_then(|name| source.locate(name, style))
+    });
+    if exact.is_some() {
+        return Ok(exact);
+    }
+
+    Ok(families.iter().map(AsRef::as_ref).find_map(|family| {
+        closest_family(family, installed.iter().map(String::as_str))
+            .and_then(|name| source.locate(name, style))
+            .or_else(|| source.locate(family, style))
+    }))
+}
+
+/// Remembers the results of [`locate_font`], for when the same fonts are located many times,
+/// such as every frame of a UI. The platform configuration is initialized on the first lookup
+/// and reused for every lookup after it.
+///
+/// Fonts that were not found are remembered too. If fonts are installed or removed while
+/// the cache is in use, call [`FontCache::clear`].
+#[derive(Default)]
+pub struct FontCache {
+    source: Option<platform::FontSource>,
+    paths: HashMap<(String, Option<String>), Option<PathBuf>>,
+}
+
+impl FontCache {
+    /// Creates an empty cache. This does not initialize the platform configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the path of the font with `family` and `style` if it has been located before,
+    /// otherwise locates it the same as [`locate_font`] and remembers the result.
+    pub fn get_or_locate<F, S>(
+        &mut self,
+        family: F,
+        style: Option<S>,
+    ) -> Result<Option<&Path>, Error>
+    where
+        F: AsRef<str>,
+        S: AsRef<str>,
+    {
+        let key = (
+            family.as_ref().to_owned(),
+            style.as_ref().map(|style| style.as_ref().to_owned()),
+        );
+
+        if !self.paths.contains_key(&key) {
+            let source = match self.source.take() {
+                Some(source) => source,
+                None => platform::FontSource::new()?,
+            };
+            let path = self.source.insert(source).locate(&key.0, key.1.as_deref());
+            self.paths.insert(key.clone(), path);
+        }
+
+        Ok(self.paths[&key].as_deref())
+    }
+
+    /// Forgets every font that has been located, and the platform configuration,
+    /// so that the next lookups see fonts that have been installed or removed since.
+    pub fn clear(&mut self) {
+        self.source = None;
+        self.paths.clear();
+    }
+}
+
+/// The largest number of single-character edits between a requested family name and an installed one
+/// for [`match_family`] to consider it a typo.
+const MAX_FAMILY_DISTANCE: usize = 2;
+
+/// Returns the name of the installed font family matching `name`, ignoring case.
+/// If there is no exact match, the closest installed family by Levenshtein distance is returned instead,
+/// so that a mistyped name such as "Ariel" still resolves to "Arial".
+///
+/// Returns [`None`] if no installed family is within a couple of edits of `name`.
+pub fn match_family(name: &str) -> Result<Option<String>, Error> {
+    let families = list_families()?;
+
+    Ok(closest_family(name, families.iter().map(String::as_str)).map(String::from))
+}
+
+fn closest_family<'a>(name: &str, families: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let name = name.to_lowercase();
+
+    families
+        .into_iter()
+        .map(|family| (levenshtein(&name, &family.to_lowercase()), family))
+        .filter(|&(distance, _)| distance <= MAX_FAMILY_DISTANCE)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, family)| family)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, &b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
 }
 
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
 
+    const FAMILIES: [&str; 4] = ["Arial", "Courier", "DejaVu Sans", "Times New Roman"];
+
+    #[test_case("Arial" => Some("Arial") ; "exact")]
+    #[test_case("arial" => Some("Arial") ; "case insensitive")]
+    #[test_case("Ariel" => Some("Arial") ; "typo")]
+    #[test_case("Courrier" => Some("Courier") ; "extra letter")]
+    #[test_case("Helvetica" => None ; "nothing close")]
+    fn closest_family(name: &str) -> Option<&'static str> {
+        super::closest_family(name, FAMILIES)
+    }
+
+    #[test]
+    fn sorted_names() {
+        let families = [
+            "cantarell",
+            "DejaVu Sans",
+            "Cantarell",
+            "arial",
+            "DejaVu Sans",
+        ];
+
+        assert_eq!(
+            super::sorted_names(families.map(String::from).to_vec()),
+            ["arial", "Cantarell", "cantarell", "DejaVu Sans"]
+        );
+    }
+
+    #[test_case("kitten", "sitting" => 3)]
+    #[test_case("", "abc" => 3)]
+    #[test_case("same", "same" => 0)]
+    fn levenshtein(a: &str, b: &str) -> usize {
+        super::levenshtein(a, b)
+    }
+
     #[test_case("Arial", None ; "test locate Arial")]
     #[test_case("Monospace", None ; "test locate Monospace")]
     #[test_case("Times New Roman", None ; "test locate Times New Roman")]
@@ -53,4 +282,86 @@ mod tests {
             _ => panic!("fontconfig failed to initialize or a font wasn't found"),
         }
     }
+
+    #[test_case(&["Not A Real Family", "Monospace"] ; "test locate fallback Monospace")]
+    #[test_case(&["Not A Real Family", "sans-serif"] ; "test locate fallback sans-serif")]
+    fn locate_font_fallback(families: &[&str]) {
+        // The generic families fall back to any installed font, so there must be at least one.
+        if super::list_families().unwrap().is_empty() {
+            return;
+        }
+
+        assert!(super::locate_font_fallback(families, None)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn font_cache() {
+        let mut cache = super::FontCache::new();
+        let located = super::locate_font("Monospace", None::<&str>).unwrap();
+
+        for _ in 0..2 {
+            let cached = cache.get_or_locate("Monospace", None::<&str>).unwrap();
+            assert_eq!(cached, located.as_deref());
+        }
+        assert_eq!(cache.paths.len(), 1);
+
+        cache.clear();
+        assert!(cache.paths.is_empty() && cache.source.is_none());
+    }
+
+    #[test]
+    fn io_error_is_wrapped() {
+        let path = std::path::Path::new("/nonexistent/font.ttf");
+
+        assert!(matches!(super::covers(path, "a"), Err(super::Error::Io(_))));
+        assert!(matches!(
+            super::missing_glyphs(path, "a"),
+            Err(super::Error::Io(_))
+        ));
+    }
+
+    /// Returns `true` if `family` is installed, so that tests which need it can be skipped otherwise.
+    #[cfg(target_os = "linux")]
+    fn is_installed(family: &str) -> bool {
+        super::list_families()
+            .unwrap()
+            .iter()
+            .any(|name| name == family)
+    }
+
+    // DejaVu Sans is installed on most Linux distributions, with bold and oblique faces
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn list_styles() {
+        if !is_installed("DejaVu Sans") {
+            return;
+        }
+        let styles = super::list_styles("DejaVu Sans").unwrap();
+
+        assert!(styles.len() > 1, "only found {styles:?}");
+        assert!(styles.iter().any(|style| style == "Bold"));
+        assert_eq!(styles, super::sorted_names(styles.clone()));
+        assert!(super::list_styles("Not A Real Family").unwrap().is_empty());
+        assert_eq!(super::styles("DejaVu Sans").unwrap(), styles);
+    }
+
+    // DejaVu Sans is installed on most Linux distributions, and has no CJK glyphs
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn latin_font_does_not_cover_cjk() {
+        // fontconfig falls back to another font when DejaVu Sans is not installed
+        let path = match super::locate_font("DejaVu Sans", None::<&str>) {
+            Ok(Some(path)) if path.file_stem().is_some_and(|stem| stem == "DejaVuSans") => path,
+            _ => return,
+        };
+
+        assert!(super::covers(&path, "Hello, world!").unwrap());
+        assert!(!super::covers(&path, "Hello, 世界").unwrap());
+        assert_eq!(
+            super::missing_glyphs(&path, "Hello, 世界").unwrap(),
+            Some('世')
+        );
+    }
 }
@@ -13,3 +13,251 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+
+use super::Error;
+
+use std::{
+    ffi::{OsStr, OsString},
+    os::windows::ffi::{OsStrExt, OsStringExt},
+    path::PathBuf,
+    ptr,
+};
+
+use winapi::{
+    shared::{
+        minwindef::{DWORD, HKEY},
+        winerror::{ERROR_FILE_NOT_FOUND, ERROR_MORE_DATA, ERROR_NO_MORE_ITEMS, ERROR_SUCCESS},
+    },
+    um::{
+        winnt::{KEY_READ, REG_EXPAND_SZ, REG_SZ},
+        winreg::{
+            RegCloseKey, RegEnumValueW, RegOpenKeyExW, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE,
+        },
+    },
+};
+
+/// The registry key listing installed fonts, under both `HKEY_LOCAL_MACHINE` for fonts installed
+/// for every user, and `HKEY_CURRENT_USER` for fonts installed for only the current user.
+const FONTS_KEY: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Fonts";
+
+/// Words that may follow the family in a registered font name, such as `Arial Bold Italic`.
+const STYLE_WORDS: [&str; 12] = [
+    "Regular",
+    "Italic",
+    "Oblique",
+    "Bold",
+    "Semibold",
+    "Light",
+    "Semilight",
+    "Medium",
+    "Thin",
+    "ExtraLight",
+    "ExtraBold",
+    "Condensed",
+];
+
+/// A value from the fonts registry key, such as `Arial Bold (TrueType)` with the data `arialbd.ttf`.
+struct RegisteredFont {
+    /// The full names of the faces in the font file, such as `Arial Bold`.
+    /// A font collection has more than one, such as `Cambria & Cambria Math`.
+    faces: Vec<String>,
+    path: PathBuf,
+}
+
+/// Closes the registry key when dropped.
+struct RegistryKey(HKEY);
+
+impl Drop for RegistryKey {
+    fn drop(&mut self) {
+        unsafe { RegCloseKey(self.0) };
+    }
+}
+
+/// Locate a font on the filesystem by deferring to platform-specific APIs.
+///
+/// On Windows the installed fonts are read from the registry. If the family is installed but
+/// has no face with the requested style, the regular face of the family is returned instead,
+/// the same as fontconfig would on Linux.
+pub fn locate_font<F, S>(family: F, style: Option<S>) -> Result<Option<PathBuf>, Error>
+where
+    F: AsRef<str>,
+    S: AsRef<str>,
+{
+    Ok(FontSource::new()?.locate(family.as_ref(), style.as_ref().map(S::as_ref)))
+}
+
+/// The fonts read from the registry, to locate many fonts without reading it each time.
+/// See [`super::FontCache`].
+pub(super) struct FontSource(Vec<RegisteredFont>);
+
+impl FontSource {
+    pub(super) fn new() -> Result<Self, Error> {
+        registered_fonts().map(Self)
+    }
+
+    pub(super) fn locate(&self, family: &str, style: Option<&str>) -> Option<PathBuf> {
+        let style = style.filter(|style| !style.eq_ignore_ascii_case("Regular"));
+        let find = |style: Option<&str>| {
+            self.0
+                .iter()
+                .find(|font| {
+                    font.faces
+                        .iter()
+                        .any(|face| face_matches(face, family, style))
+                })
+                .map(|font| font.path.clone())
+        };
+
+        find(style).or_else(|| find(None))
+    }
+}
+
+/// List the family of every installed font, in no particular order and with duplicates.
+/// See [`super::list_families`].
+///
+/// The registry only has the full name of each face, so the family is found by removing
+/// common style words from the end of the name, such as `Bold Italic` from `Arial Bold Italic`.
+pub(super) fn family_names() -> Result<Vec<String>, Error> {
+    Ok(registered_fonts()?
+        .into_iter()
+        .flat_map(|font| font.faces)
+        .map(|face| strip_style(&face).to_owned())
+        .collect())
+}
+
+/// List the style of every installed font of `family`, in no particular order and with duplicates.
+/// See [`super::list_styles`].
+pub(super) fn style_names(family: &str) -> Result<Vec<String>, Error> {
+    Ok(registered_fonts()?
+        .iter()
+        .flat_map(|font| &font.faces)
+        .filter_map(|face| face_style(face, family))
+        .map(|style| match style {
+            "" => String::from("Regular"),
+            style => style.to_owned(),
+        })
+        .collect())
+}
+
+fn face_matches(face: &str, family: &str, style: Option<&str>) -> bool {
+    match (face_style(face, family), style) {
+        (Some(rest), None) => rest.is_empty() || rest.eq_ignore_ascii_case("Regular"),
+        (Some(rest), Some(style)) => rest.eq_ignore_ascii_case(style),
+        (None, _) => false,
+    }
+}
+
+/// Returns the rest of the full name of `face` after `family`, such as `Bold` from `Arial Bold`,
+/// or [`None`] if the face is not of the family.
+fn face_style<'a>(face: &'a str, family: &str) -> Option<&'a str> {
+    match face.get(..family.len()) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(family) => {
+            let rest = &face[family.len()..];
+
+            (rest.is_empty() || rest.starts_with(' ')).then(|| rest.trim())
+        }
+        _ => None,
+    }
+}
+
+fn strip_style(face: &str) -> &str {
+    let mut family = face;
+
+    while let Some((rest, word)) = family.rsplit_once(' ') {
+        if !STYLE_WORDS
+            .iter()
+            .any(|style| style.eq_ignore_ascii_case(word))
+        {
+            break;
+        }
+        family = rest;
+    }
+
+    family
+}
+
+fn registered_fonts() -> Result<Vec<RegisteredFont>, Error> {
+    let mut fonts = read_fonts_key(HKEY_LOCAL_MACHINE)?;
+    fonts.extend(read_fonts_key(HKEY_CURRENT_USER)?);
+
+    Ok(fonts)
+}
+
+fn read_fonts_key(root: HKEY) -> Result<Vec<RegisteredFont>, Error> {
+    let subkey = OsStr::new(FONTS_KEY)
+        .encode_wide()
+        .chain([0])
+        .collect::<Vec<_>>();
+    let mut key = ptr::null_mut();
+
+    // The per-user key does not exist on versions of Windows before 10.
+    match unsafe { RegOpenKeyExW(root, subkey.as_ptr(), 0, KEY_READ, &mut key) } as DWORD {
+        ERROR_SUCCESS => {}
+        ERROR_FILE_NOT_FOUND => return Ok(Vec::new()),
+        code => return Err(std::io::Error::from_raw_os_error(code as i32).into()),
+    }
+    let key = RegistryKey(key);
+
+    // Relative paths are in the system fonts directory, whereas per-user fonts have absolute paths.
+    let fonts_dir = std::env::var_os("WINDIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(r"C:\Windows"))
+        .join("Fonts");
+
+    // Value names are limited to 16383 characters, and paths to 32767.
+    let mut name = vec![0u16; 16384];
+    let mut data = vec![0u16; 32768];
+    let mut fonts = Vec::new();
+
+    for index in 0.. {
+        let mut name_len = name.len() as DWORD;
+        let mut data_len = (data.len() * 2) as DWORD;
+        let mut kind = 0;
+
+        let status = unsafe {
+            RegEnumValueW(
+                key.0,
+                index,
+                name.as_mut_ptr(),
+                &mut name_len,
+                ptr::null_mut(),
+                &mut kind,
+                data.as_mut_ptr().cast(),
+                &mut data_len,
+            )
+        } as DWORD;
+
+        match status {
+            ERROR_SUCCESS => {}
+            ERROR_NO_MORE_ITEMS => break,
+            // The value is not a path if it is this long, so skip it.
+            ERROR_MORE_DATA => continue,
+            code => return Err(std::io::Error::from_raw_os_error(code as i32).into()),
+        }
+        if kind != REG_SZ && kind != REG_EXPAND_SZ {
+            continue;
+        }
+
+        let name = String::from_utf16_lossy(&name[..name_len as usize]);
+        let file = &data[..data_len as usize / 2];
+        let file = PathBuf::from(OsString::from_wide(
+            match file.iter().position(|&c| c == 0) {
+                Some(end) => &file[..end],
+                None => file,
+            },
+        ));
+
+        // Strip the format suffix, such as ` (TrueType)`.
+        let name = name
+            .strip_suffix(')')
+            .and_then(|name| name.rsplit_once(" ("))
+            .map_or(name.as_str(), |(name, _)| name);
+
+        fonts.push(RegisteredFont {
+            faces: name.split(" & ").map(str::to_owned).collect(),
+            path: fonts_dir.join(file),
+        });
+    }
+
+    Ok(fonts)
+}
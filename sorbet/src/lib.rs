@@ -144,15 +144,93 @@ pub trait Bounded: Dimensioned + Positioned {
     fn bottom_right(&self) -> math::Point {
         math::point(self.right(), self.bottom())
     }
+
+    /// Returns the distance from `point` to the nearest edge or vertex of the bounding box,
+    /// or `0.0` if the point is inside of it. This is useful for hit-testing with a tolerance,
+    /// such as deciding whether a click landed close enough to a widget.
+    fn distance_to_point(&self, point: math::Point) -> f32 {
+        let dx = (self.left() - point.x).max(point.x - self.right()).max(0.0);
+        let dy = (self.top() - point.y).max(point.y - self.bottom()).max(0.0);
+
+        dx.hypot(dy)
+    }
+
+    /// Returns the largest [`math::Rect`] with the aspect ratio `content_aspect` (width divided by height)
+    /// that fits inside of the bounding box, centered on it. This is useful for placing an image
+    /// inside of a widget without stretching it, leaving bars on two sides (letterboxing).
+    ///
+    /// If the bounding box has no height, this returns [`math::Rect::zero()`].
+    fn aspect_fit(&self, content_aspect: f32) -> math::Rect {
+        if self.height() <= 0.0 {
+            return math::Rect::zero();
+        }
+
+        let size = if content_aspect > self.width() / self.height() {
+            math::size(self.width(), self.width() / content_aspect)
+        } else {
+            math::size(self.height() * content_aspect, self.height())
+        };
+        let x = self.x() + (self.width() - size.width) / 2.0;
+        let y = self.y() + (self.height() - size.height) / 2.0;
+
+        math::Rect::new(math::point(x, y), size)
+    }
 }
 
 impl<T> Bounded for T where T: Dimensioned + Positioned {}
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    struct Bounds(math::Rect);
+
+    impl Dimensioned for Bounds {
+        fn size(&self) -> math::Size {
+            self.0.size
+        }
+    }
+
+    impl Positioned for Bounds {
+        fn position(&self) -> math::Point {
+            self.0.origin
+        }
+    }
+
     #[test]
     fn it_works() {
         let result = 2 + 2;
         assert_eq!(result, 4);
     }
+
+    #[test]
+    fn test_distance_to_point() {
+        let bounds = Bounds(math::rect(10.0, 10.0, 20.0, 10.0));
+
+        // Inside
+        assert_eq!(bounds.distance_to_point(math::point(15.0, 15.0)), 0.0);
+        // Off the bottom-right corner
+        assert_eq!(bounds.distance_to_point(math::point(33.0, 24.0)), 5.0);
+        // Off the left edge
+        assert_eq!(bounds.distance_to_point(math::point(4.0, 12.0)), 6.0);
+    }
+
+    #[test]
+    fn test_aspect_fit() {
+        let square = Bounds(math::rect(100.0, 100.0, 160.0, 160.0));
+
+        // A horizontal band across the middle of the square
+        assert_eq!(
+            square.aspect_fit(16.0 / 9.0),
+            math::rect(100.0, 135.0, 160.0, 90.0)
+        );
+        // A vertical band down the middle of the square
+        assert_eq!(
+            square.aspect_fit(0.5),
+            math::rect(140.0, 100.0, 80.0, 160.0)
+        );
+
+        let flat = Bounds(math::rect(10.0, 10.0, 100.0, 0.0));
+        assert_eq!(flat.aspect_fit(16.0 / 9.0), math::Rect::zero());
+    }
 }